@@ -0,0 +1,69 @@
+use std::{fs, sync::Arc};
+
+use mchess::{book::BookBuilder, moves::{magic::Magic, tables::AttackTables}, pgn::MainLineReader};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let default_output = "book.bin";
+    let default_depth = 20;
+
+    if args.len() < 2 || args[1] == "--help" || args[1] == "-h" {
+        println!("Usage: book [-o FILE] [-d PLIES] PGN...");
+        println!("  PGN             - Games to build the book from");
+        println!("  -o (--output)   - Polyglot book to write (default: book.bin)");
+        println!("  -d (--depth)    - Plies of every game to keep (default: 20)");
+        return;
+    }
+
+    let value = |names: [&str; 2]| args.iter()
+        .position(|arg| names.contains(&arg.as_str()))
+        .and_then(|i| args.get(i + 1));
+
+    let output = value(["-o", "--output"]).map_or(default_output, |s| s.as_str());
+
+    let depth = value(["-d", "--depth"])
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(default_depth);
+
+    let mut builder = BookBuilder::new(depth, magic.clone(), attacks.clone());
+    let mut games = 0;
+
+    let mut i = 1;
+    while i < args.len() {
+        if args[i].starts_with('-') {
+            i += 2;
+            continue;
+        }
+
+        let text = match fs::read_to_string(&args[i]) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{}: {}", args[i], e);
+                std::process::exit(1);
+            }
+        };
+
+        // a game that can't be read doesn't stop the others from being added
+        for game in MainLineReader::new(&text, magic.clone(), attacks.clone()) {
+            match game.map_err(|e| e.to_string()).and_then(|game| builder.add_game(&game).map_err(|e| e.to_string())) {
+                Ok(()) => games += 1,
+                Err(e) => eprintln!("{}: {}", args[i], e)
+            }
+        }
+
+        i += 1;
+    }
+
+    let book = builder.build();
+
+    if let Err(e) = book.save(output) {
+        eprintln!("{}: {}", output, e);
+        std::process::exit(1);
+    }
+
+    println!("{} games, {} entries written to {}", games, book.entries.len(), output);
+}
\ No newline at end of file
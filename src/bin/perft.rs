@@ -33,7 +33,13 @@ fn main() {
         .and_then(|s| s.parse::<u32>().ok())
         .unwrap_or(default_depth);
 
-    let mut board = Board::from_fen(fen, magic.clone(), attacks.clone());
+    let mut board = match Board::from_fen(fen, magic.clone(), attacks.clone()) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
 
     if !short {
         println!("Position:");
@@ -50,6 +56,7 @@ fn main() {
 
     if !short {
         println!("Time: {:?}", duration);
+        println!("NPS: {}", (nodes as f64 / duration.as_secs_f64()) as u64);
     }
 
     println!("{}", nodes);
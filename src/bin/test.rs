@@ -14,5 +14,5 @@ fn main() {
     println!("{}", search.search(&mut board, 6));
 
     println!("{:?}", start.elapsed());
-    println!("{} {} {}", search.nodes, search.tt_hits, search.tt.len());
+    println!("{} {} {}", search.nodes, search.tt_hits, search.tt.hashfull());
 }
\ No newline at end of file
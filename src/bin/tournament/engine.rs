@@ -3,6 +3,7 @@ use std::{cmp::Ordering, io::{BufRead, BufReader, Write}, process::{Child, Comma
 pub struct Engine {
     pub name: String,
     process: Child,
+    options: Vec<(String, String)>,
     pub stats: EngineStats
 }
 
@@ -44,7 +45,7 @@ impl EngineStats {
 }
 
 impl Engine {
-    pub fn new(path: &str, name: &str) -> Result<Self, String> {
+    pub fn new(path: &str, name: &str, options: &[(String, String)]) -> Result<Self, String> {
         let process = Command::new(path)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -55,6 +56,7 @@ impl Engine {
         Ok(Engine {
             name: name.to_owned(),
             process,
+            options: options.to_vec(),
             stats: EngineStats::new()
         })
     }
@@ -115,6 +117,10 @@ impl Engine {
             return Err("Engine did not respond to uci".to_string());
         }
 
+        for (name, value) in self.options.clone() {
+            self.send(&format!("setoption name {} value {}", name, value))?;
+        }
+
         self.send("isready")?;
         let response = self.get(Some("readyok"))?;
         if !response.contains("readyok") {
@@ -1,6 +1,6 @@
-use std::sync::Arc;
+use std::{fs::OpenOptions, io::Write, sync::Arc};
 
-use mchess::{board::Board, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}};
+use mchess::{board::Board, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}, pgn::{self, GameResult}};
 
 use crate::engine::Engine;
 
@@ -11,13 +11,7 @@ pub struct Game {
     pub time_limit: u64,
     pub magic: Arc<Magic>,
     pub attacks: Arc<AttackTables>,
-}
-
-#[derive(Clone, Copy)]
-pub enum GameResult {
-    WhiteWin,
-    BlackWin,
-    Draw
+    pub pgn: Option<String>, // the file every finished game is appended to
 }
 
 impl Game {
@@ -28,12 +22,17 @@ impl Game {
 
         for game in 0..self.games_per_match {
             println!("{}-{}, game {}/{}", engine1.name, engine2.name, game, self.games_per_match);
-            let result = if game % 2 == 0 {
+            let mut record = if game % 2 == 0 {
                 self.play_game(&mut *engine1, &mut *engine2)?
             } else {
                 self.play_game(&mut *engine2, &mut *engine1)?
             };
-        
+
+            record.set_tag("Round", &(game + 1).to_string());
+
+            self.save_game(&record)?;
+
+            let result = record.result;
             results.push(result);
 
             match result {
@@ -59,7 +58,8 @@ impl Game {
                         engine2.stats.losses += 1;
                     }
                 },
-                GameResult::Draw => {
+                // games cut off at the move limit count as draws
+                GameResult::Draw | GameResult::Unknown => {
                     engine1.stats.draws += 1;
                     engine2.stats.draws += 1;
                     engine1.stats.score += 0.5;
@@ -71,7 +71,7 @@ impl Game {
         Ok(results)
     }
 
-    pub fn play_game(&self, white: &mut Engine, black: &mut Engine) -> Result<GameResult, String> {
+    pub fn play_game(&self, white: &mut Engine, black: &mut Engine) -> Result<pgn::Game, String> {
         white.init()?;
         black.init()?;
 
@@ -79,54 +79,50 @@ impl Game {
 
         let mut board = Board::startpos(self.magic.clone(), self.attacks.clone());
 
-        for _ in 0..400 {
-            let white_move = white.get_best_move(STARTPOS, &moves, self.time_limit)?;
+        let mut record = pgn::Game::new();
+        record.set_tag("Event", "mchess tournament");
+        record.set_tag("White", &white.name);
+        record.set_tag("Black", &black.name);
 
-            let legal_moves = board.get_legal_moves();
+        for ply in 0..800 {
+            let engine = if ply % 2 == 0 { &mut *white } else { &mut *black };
+            let best_move = engine.get_best_move(STARTPOS, &moves, self.time_limit)?;
 
-            if moves.is_empty() && !board.is_checked(board.turn) {
-                return Ok(GameResult::Draw)
-            }
+            let legal_moves = board.get_legal_moves();
 
             for m in legal_moves {
-                if format!("{}", MoveDisplay(m)) == white_move {
+                if format!("{}", MoveDisplay(m)) == best_move {
                     board.make_move(m);
+                    record.moves.push(m);
                 }
             }
 
-            moves.push(white_move);
+            moves.push(best_move);
 
-            if board.is_checkmate() {
-                return Ok(GameResult::WhiteWin);
-            }
-            if board.is_draw() {
-                return Ok(GameResult::Draw);
+            if let Some(outcome) = board.outcome() {
+                record.result = outcome.into();
+                return Ok(record);
             }
+        }
 
-            let black_move = black.get_best_move(STARTPOS, &moves, self.time_limit)?;
-
-            let legal_moves = board.get_legal_moves();
-
-            if moves.is_empty() && !board.is_checked(board.turn) {
-                return Ok(GameResult::Draw)
-            }
+        Ok(record)
+    }
 
-            for m in legal_moves {
-                if format!("{}", MoveDisplay(m)) == black_move {
-                    board.make_move(m);
-                }
-            }
+    fn save_game(&self, record: &pgn::Game) -> Result<(), String> {
+        let Some(path) = &self.pgn else {
+            return Ok(());
+        };
 
-            moves.push(black_move);
+        let pgn = record.to_pgn(self.magic.clone(), self.attacks.clone())
+            .map_err(|e| e.to_string())?;
 
-            if board.is_checkmate() {
-                return Ok(GameResult::BlackWin);
-            }
-            if board.is_draw() {
-                return Ok(GameResult::Draw);
-            }
-        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path, e))?;
 
-        Ok(GameResult::Draw)
+        writeln!(file, "{}", pgn)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))
     }
 }
\ No newline at end of file
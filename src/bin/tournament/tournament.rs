@@ -7,6 +7,7 @@ use crate::{display::display_results, engine::Engine, game::Game};
 pub struct EngineInfo {
     pub name: String,
     pub path: String,
+    pub options: Vec<(String, String)>,
 }
 
 pub struct Tournament {
@@ -22,17 +23,25 @@ impl Tournament {
         let mut engines = Vec::new();
         let mut games_per_match = 2;
         let mut time_limit = 4000;
+        let mut pgn = None;
 
         for line in config.lines() {
             if line.trim().starts_with("engine:") {
                 let parts: Vec<&str> = line["engine:".len()..].split_whitespace().collect();
 
-                if parts.len() == 2 {
+                // engine: <path> <name> [<option>=<value> ...]
+                if parts.len() >= 2 {
                     let path = parts[0].to_string();
                     let name = parts[1].trim_matches('"').to_string();
+                    let options = parts[2..].iter()
+                        .filter_map(|option| option.split_once('='))
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect();
+
                     engines.push(EngineInfo {
                         path,
-                        name
+                        name,
+                        options
                     });
                 }
             } else if line.trim().starts_with("games:") {
@@ -43,6 +52,8 @@ impl Tournament {
                 if let Ok(value) = line["time:".len()..].trim().parse::<u32>() {
                     time_limit = value as u64;
                 }
+            } else if line.trim().starts_with("pgn:") {
+                pgn = Some(line["pgn:".len()..].trim().to_string());
             }
         }
 
@@ -58,7 +69,7 @@ impl Tournament {
         let attacks = Arc::new(AttackTables::new());
 
         let engines = engines.iter()
-            .map(|info| Engine::new(&info.path, &info.name))
+            .map(|info| Engine::new(&info.path, &info.name, &info.options))
             .collect::<Result<Vec<Engine>, _>>()?;
 
         let mut tournament = Tournament {
@@ -68,6 +79,7 @@ impl Tournament {
                 time_limit,
                 magic,
                 attacks,
+                pgn,
             }
         };
 
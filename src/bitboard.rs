@@ -25,6 +25,24 @@ pub struct Bitboard {
     pub black_attacks: u64,
 }
 
+impl Default for Bitboard {
+    fn default() -> Self {
+        Bitboard::new()
+    }
+}
+
+// the attack maps are left behind by move generation, they aren't part of the position
+impl PartialEq for Bitboard {
+    fn eq(&self, other: &Self) -> bool {
+        self.white_king == other.white_king && self.black_king == other.black_king
+            && self.white_queens == other.white_queens && self.black_queens == other.black_queens
+            && self.white_rooks == other.white_rooks && self.black_rooks == other.black_rooks
+            && self.white_bishops == other.white_bishops && self.black_bishops == other.black_bishops
+            && self.white_knights == other.white_knights && self.black_knights == other.black_knights
+            && self.white_pawns == other.white_pawns && self.black_pawns == other.black_pawns
+    }
+}
+
 impl Bitboard {
     pub fn new() -> Self {
         Bitboard {
@@ -103,6 +121,23 @@ impl Bitboard {
         self.empty |= square;
     }
 
+    pub fn get_pieces(&self, piece_type: PieceType, color: PieceColor) -> u64 {
+        match (piece_type, color) {
+            (PieceType::Pawn, PieceColor::White) => self.white_pawns,
+            (PieceType::Knight, PieceColor::White) => self.white_knights,
+            (PieceType::Bishop, PieceColor::White) => self.white_bishops,
+            (PieceType::Rook, PieceColor::White) => self.white_rooks,
+            (PieceType::Queen, PieceColor::White) => self.white_queens,
+            (PieceType::King, PieceColor::White) => self.white_king,
+            (PieceType::Pawn, PieceColor::Black) => self.black_pawns,
+            (PieceType::Knight, PieceColor::Black) => self.black_knights,
+            (PieceType::Bishop, PieceColor::Black) => self.black_bishops,
+            (PieceType::Rook, PieceColor::Black) => self.black_rooks,
+            (PieceType::Queen, PieceColor::Black) => self.black_queens,
+            (PieceType::King, PieceColor::Black) => self.black_king,
+        }
+    }
+
     pub fn remove_piece_at(&mut self, square: u64) {
         let piece = self.get_piece_at(square);
 
@@ -166,6 +201,14 @@ impl Bitboard {
         material.count_ones()
     }
 
+    pub fn has_non_pawn_material(&self, color: PieceColor) -> bool {
+        if color == PieceColor::White {
+            self.white_pieces & !(self.white_pawns | self.white_king) != 0
+        } else {
+            self.black_pieces & !(self.black_pawns | self.black_king) != 0
+        }
+    }
+
     pub fn count_material(&self, color: PieceColor) -> u32 {
         if color == PieceColor::White {
             self.white_pawns.count_ones()   * PieceType::Pawn.value()   +
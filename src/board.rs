@@ -1,10 +1,8 @@
 use std::sync::Arc;
 
-use rand::{rngs::StdRng, Rng, SeedableRng};
+use crate::{bitboard::Bitboard, moves::{magic::Magic, tables::AttackTables, Position}, piece::{Piece, PieceColor}, zobrist};
 
-use crate::{bitboard::Bitboard, moves::{magic::Magic, tables::AttackTables, Position}, piece::{Piece, PieceColor, PieceType}};
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Castling {
     pub white: (bool, bool),
     pub black: (bool, bool)
@@ -26,20 +24,70 @@ impl Castling {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Checkmate(PieceColor), // winning side
+    Stalemate,
+    FiftyMoves,
+    Repetition,
+    InsufficientMaterial
+}
 
+impl Outcome {
+    pub fn is_draw(&self) -> bool {
+        !matches!(self, Outcome::Checkmate(_))
+    }
+
+    pub fn winner(&self) -> Option<PieceColor> {
+        match self {
+            Outcome::Checkmate(color) => Some(*color),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Board {
     pub bb: Bitboard,
     pub turn: PieceColor,
     pub moves: u32,
     pub halfmove_clock: u32,
     pub target_square: u64,
-    pub hash: i64,
-    pub hash_table: [i64; 781],
+    pub hash: u64,
+    pub history: Vec<u64>,
     pub castling: Castling,
     pub magic: Arc<Magic>,
     pub attacks: Arc<AttackTables>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenError {
+    FieldCount(usize),
+    RankCount(usize),
+    RankLength { rank: usize, length: usize }, // the number of squares the rank describes
+    InvalidPiece(char),
+    KingCount(PieceColor),                     // not exactly one king
+    SideToMove(String),
+    Castling(String),
+    EnPassant(String),
+    Clock(String),
+}
+
+impl std::error::Error for FenError {}
+
+// the same position as far as a FEN can tell, an en passant square nobody can
+// take on and the history of the game don't make a difference
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.bb == other.bb
+            && self.turn == other.turn
+            && self.castling == other.castling
+            && self.en_passant_square() == other.en_passant_square()
+            && self.halfmove_clock == other.halfmove_clock
+            && self.moves == other.moves
+    }
+}
+
 impl Board {
     pub fn new(magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Self {
         Board {
@@ -49,7 +97,7 @@ impl Board {
             halfmove_clock: 0,
             target_square: 0,
             hash: 0,
-            hash_table: [0; 781],
+            history: Vec::with_capacity(256),
             castling: Castling {
                 white: (true, true),
                 black: (true, true)
@@ -59,112 +107,185 @@ impl Board {
         }
     }
 
-    pub fn from_fen(fen: &str, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Self {
+    pub fn from_fen(fen: &str, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Result<Self, FenError> {
         let mut board = Board::new(magic, attacks);
         let parts: Vec<&str> = fen.split_whitespace().collect();
-        let position = parts[0];
-        let turn = parts[1];
-        let c = parts[2];
-        let target_square = parts[3];
-        let halfmoves = parts[4];
-        let moves = parts[5];
 
-        let ranks: Vec<&str> = position.split('/').collect();
+        // the clocks are left out often enough to make them optional
+        if !(4..=6).contains(&parts.len()) {
+            return Err(FenError::FieldCount(parts.len()));
+        }
+
+        let ranks: Vec<&str> = parts[0].split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(FenError::RankCount(ranks.len()));
+        }
 
         for (j, rank) in ranks.iter().enumerate() {
             let mut i = 0;
-            for char in rank.chars().into_iter() {
-                if char.is_ascii_digit() {
-                    i += char.to_digit(10).unwrap() as usize - 1;
-                } else {
-                    let color = if "PNBRQK".contains(char) {
-                        PieceColor::White
-                    } else {
-                        PieceColor::Black
-                    };
-
-                    let piece_type = match char.to_ascii_lowercase() {
-                        'p' => PieceType::Pawn,
-                        'n' => PieceType::Knight,
-                        'b' => PieceType::Bishop,
-                        'r' => PieceType::Rook,
-                        'q' => PieceType::Queen,
-                        'k' => PieceType::King,
-                        a => panic!("Invalid piece type: expected one of PNBRQK, found {}", a)
-                    };
-
-                    let piece = Piece {
-                        color,
-                        piece_type
-                    };
+            for char in rank.chars() {
+                if let Some(empty) = char.to_digit(10).filter(|empty| (1..=8).contains(empty)) {
+                    i += empty as usize;
+                    continue;
+                }
 
+                let piece = Piece::from_char(char).ok_or(FenError::InvalidPiece(char))?;
+
+                // a rank that is too long is reported below, the piece can't spill into the next one
+                if i < 8 {
                     board.bb.add_piece(piece, Position::bitboard(i, j));
                 }
+
                 i += 1;
             }
+
+            if i != 8 {
+                return Err(FenError::RankLength { rank: 8 - j, length: i });
+            }
         }
 
-        if board.bb.white_king == 0 || board.bb.black_king == 0 {
-            panic!("Invalid position: no king")
+        if board.bb.white_king.count_ones() != 1 {
+            return Err(FenError::KingCount(PieceColor::White));
         }
 
-        board.turn = if turn == "b" { PieceColor::Black } else { PieceColor::White };
-        board.halfmove_clock = halfmoves.parse().unwrap_or(0);
-        board.moves = moves.parse().unwrap_or(1);
+        if board.bb.black_king.count_ones() != 1 {
+            return Err(FenError::KingCount(PieceColor::Black));
+        }
+
+        board.turn = match parts[1] {
+            "w" => PieceColor::White,
+            "b" => PieceColor::Black,
+            turn => return Err(FenError::SideToMove(turn.to_string()))
+        };
+
+        let c = parts[2];
+
+        let duplicates = c.chars().enumerate().any(|(i, a)| c.chars().skip(i + 1).any(|b| a == b));
+
+        if c != "-" && (duplicates || !c.chars().all(|c| "KQkq".contains(c))) {
+            return Err(FenError::Castling(c.to_string()));
+        }
 
         board.castling.white = (c.contains('K'), c.contains('Q'));
         board.castling.black = (c.contains('k'), c.contains('q'));
 
-        if target_square.len() > 0 && target_square != "-" {
+        let target_square = parts[3];
+
+        if target_square != "-" {
+            // the pawn that moved two squares belongs to the side that isn't moving
+            let rank = if board.turn == PieceColor::White { '6' } else { '3' };
+            let chars: Vec<char> = target_square.chars().collect();
+
+            if chars.len() != 2 || !('a'..='h').contains(&chars[0]) || chars[1] != rank {
+                return Err(FenError::EnPassant(target_square.to_string()));
+            }
+
             board.target_square = Position {
-                x: "abcdefgh".find(target_square.chars().next().unwrap()).unwrap(),
-                y: 8 - target_square[1..].parse::<usize>().unwrap()
+                x: (chars[0] as u8 - b'a') as usize,
+                y: 8 - chars[1].to_digit(10).unwrap() as usize
             }.to_bitboard();
         }
 
+        let clock = |index: usize, default: u32| parts.get(index).map_or(Ok(default), |clock| {
+            clock.parse().map_err(|_| FenError::Clock(clock.to_string()))
+        });
+
+        board.halfmove_clock = clock(4, 0)?;
+        board.moves = clock(5, 1)?;
+
         board.gen_hash();
 
-        board
+        Ok(board)
     }
 
-    pub fn startpos(magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Self {
-        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", magic, attacks)
-    }
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
 
-    pub fn gen_hash(&mut self) {
-        let mut hash_array = [0; 781];
-        let mut hash = i64::MAX;
+        for y in 0..8 {
+            let mut empty = 0;
 
-        let mut rng = StdRng::seed_from_u64(247);
+            for x in 0..8 {
+                match self.bb.get_piece_at(Position::bitboard(x, y)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
 
-        for i in 0..(64 * 12 + 4 + 1 + 8) {
-            hash_array[i] = rng.random::<i64>();
+                        fen.push(piece.to_char());
+                    },
+                    None => empty += 1
+                }
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+
+            if y < 7 {
+                fen.push('/');
+            }
         }
 
-        for square in 0..64 {
-            if let Some(piece) = self.bb.get_piece_at(square) {
+        fen.push_str(if self.turn == PieceColor::White { " w " } else { " b " });
+
+        let castling = [
+            (self.castling.white.0, 'K'),
+            (self.castling.white.1, 'Q'),
+            (self.castling.black.0, 'k'),
+            (self.castling.black.1, 'q')
+        ].iter().filter(|(allowed, _)| *allowed).map(|(_, c)| *c).collect::<String>();
+
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+        match self.en_passant_square() {
+            0 => fen.push_str(" -"),
+            square => {
                 let pos = Position::from_bitboard(square);
-                hash ^= hash_array[piece.index() * 64 + pos.y * 8 + pos.x];
+                fen.push_str(&format!(" {}{}", (b'a' + pos.x as u8) as char, 8 - pos.y));
             }
         }
 
-        if self.castling.white.0 { hash ^= hash_array[12 * 64]; }
-        if self.castling.white.1 { hash ^= hash_array[12 * 64 + 1]; }
-        if self.castling.black.0 { hash ^= hash_array[12 * 64 + 2]; }
-        if self.castling.black.1 { hash ^= hash_array[12 * 64 + 3]; }
+        format!("{} {} {}", fen, self.halfmove_clock, self.moves)
+    }
+
+    // the en passant target square, only when a pawn is actually there to take
+    pub fn en_passant_square(&self) -> u64 {
+        if self.target_square == 0 {
+            return 0;
+        }
+
+        let pawns = if self.turn == PieceColor::White { self.bb.white_pawns } else { self.bb.black_pawns };
+        let index = self.target_square.trailing_zeros() as usize;
 
-        if self.turn == PieceColor::White {
-            hash ^= hash_array[12 * 64 + 4];
+        if self.attacks.pawn_attacks[self.turn.opposite().index()][index] & pawns != 0 {
+            self.target_square
         } else {
-            hash ^= hash_array[12 * 64 + 5];
+            0
         }
+    }
+
+    pub fn startpos(magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Self {
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", magic, attacks).unwrap()
+    }
+
+    // the Polyglot key of the position, see zobrist.rs
+    pub fn gen_hash(&mut self) {
+        let mut hash = 0;
 
-        if self.target_square != 0 {
-            let pos = Position::from_bitboard(self.target_square);
-            hash ^= hash_array[12 * 64 + 4 + 2 + pos.y];
+        for index in 0..64 {
+            let square = 1 << index;
+
+            if let Some(piece) = self.bb.get_piece_at(square) {
+                hash ^= zobrist::piece_key(piece, square);
+            }
         }
 
+        hash ^= zobrist::castling_key(&self.castling);
+        hash ^= zobrist::en_passant_key(self.en_passant_square());
+        hash ^= zobrist::turn_key(self.turn);
+
         self.hash = hash;
-        self.hash_table = hash_array;
     }
 }
\ No newline at end of file
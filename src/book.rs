@@ -0,0 +1,233 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::Arc};
+
+use rand::Rng;
+
+use crate::{board::{Board, FenError}, moves::{helper::{get_from, get_promotion, get_to, is_castling}, magic::Magic, tables::AttackTables, Move, Position}, pgn::{Game, GameResult}, piece::{PieceColor, PieceType}};
+
+// every entry of a Polyglot book is a big endian key, move, weight and learn value
+const ENTRY_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookEntry {
+    pub key: u64,
+    pub raw_move: u16,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookSelection {
+    Best,     // always the move with the highest weight
+    Weighted, // any move, as likely as its share of the weights
+}
+
+impl BookSelection {
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "Best" => Some(BookSelection::Best),
+            "Weighted" => Some(BookSelection::Weighted),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BookError {
+    Io(io::ErrorKind),
+    Size(usize), // not a whole number of entries
+}
+
+impl std::error::Error for BookError {}
+
+impl From<io::Error> for BookError {
+    fn from(e: io::Error) -> Self {
+        BookError::Io(e.kind())
+    }
+}
+
+// a Polyglot opening book, the entries are sorted by key so the moves of a position
+// are next to each other
+pub struct Book {
+    pub entries: Vec<BookEntry>,
+}
+
+impl Book {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BookError> {
+        Book::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BookError> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(BookError::Size(bytes.len()));
+        }
+
+        let entries = bytes.chunks_exact(ENTRY_SIZE).map(|chunk| BookEntry {
+            key: u64::from_be_bytes(chunk[0..8].try_into().unwrap()),
+            raw_move: u16::from_be_bytes(chunk[8..10].try_into().unwrap()),
+            weight: u16::from_be_bytes(chunk[10..12].try_into().unwrap()),
+            learn: u32::from_be_bytes(chunk[12..16].try_into().unwrap()),
+        }).collect();
+
+        Ok(Book { entries })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.entries.len() * ENTRY_SIZE);
+
+        for entry in &self.entries {
+            bytes.extend_from_slice(&entry.key.to_be_bytes());
+            bytes.extend_from_slice(&entry.raw_move.to_be_bytes());
+            bytes.extend_from_slice(&entry.weight.to_be_bytes());
+            bytes.extend_from_slice(&entry.learn.to_be_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BookError> {
+        Ok(fs::write(path, self.to_bytes())?)
+    }
+
+    pub fn lookup(&self, key: u64) -> &[BookEntry] {
+        let start = self.entries.partition_point(|entry| entry.key < key);
+        let end = start + self.entries[start..].partition_point(|entry| entry.key == key);
+
+        &self.entries[start..end]
+    }
+
+    // the legal book moves of the position with their weights, entries for moves that
+    // aren't legal here are left out
+    pub fn moves(&self, board: &mut Board) -> Vec<(Move, u16)> {
+        let legal = board.get_legal_moves();
+
+        self.lookup(board.hash).iter()
+            .filter_map(|entry| legal.iter()
+                .find(|&&m| encode_move(m) == entry.raw_move & 0x7fff)
+                .map(|&m| (m, entry.weight)))
+            .collect()
+    }
+
+    pub fn probe(&self, board: &mut Board, selection: BookSelection) -> Option<Move> {
+        let moves = self.moves(board);
+
+        match selection {
+            BookSelection::Best => moves.iter()
+                .filter(|(_, weight)| *weight > 0)
+                .max_by_key(|(_, weight)| *weight)
+                .map(|&(m, _)| m),
+            BookSelection::Weighted => {
+                let total = moves.iter().map(|&(_, weight)| weight as u32).sum::<u32>();
+
+                if total == 0 {
+                    return None;
+                }
+
+                let mut pick = rand::rng().random_range(0..total);
+
+                moves.iter().find(|&&(_, weight)| {
+                    if pick < weight as u32 {
+                        return true;
+                    }
+
+                    pick -= weight as u32;
+                    false
+                }).map(|&(m, _)| m)
+            }
+        }
+    }
+}
+
+// the plies played since the start of the game, as far as the position tells, some fens
+// count the moves from 0
+pub fn game_ply(board: &Board) -> u32 {
+    board.moves.saturating_sub(1) * 2 + if board.turn == PieceColor::Black { 1 } else { 0 }
+}
+
+// a move the way Polyglot stores it, the squares are counted from a1 and castling is
+// the king taking its own rook
+pub fn encode_move(m: Move) -> u16 {
+    let from = get_from(m);
+    let mut to = get_to(m);
+
+    if is_castling(m) {
+        to = if from << 2 == to { to << 1 } else { to >> 2 };
+    }
+
+    let promotion = match get_promotion(m) {
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(PieceType::Queen) => 4,
+        _ => 0
+    };
+
+    (promotion << 12 | square_index(from) << 6 | square_index(to)) as u16
+}
+
+fn square_index(square: u64) -> u32 {
+    let pos = Position::from_bitboard(square);
+
+    (8 * (7 - pos.y) + pos.x) as u32
+}
+
+// counts the moves of a set of games into a book, a move gets two points for a win
+// of the side that played it and one for a draw like in Polyglot's own books
+pub struct BookBuilder {
+    pub max_ply: u32,
+    scores: HashMap<(u64, u16), u32>,
+    magic: Arc<Magic>,
+    attacks: Arc<AttackTables>,
+}
+
+impl BookBuilder {
+    pub fn new(max_ply: u32, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Self {
+        BookBuilder {
+            max_ply,
+            scores: HashMap::new(),
+            magic,
+            attacks,
+        }
+    }
+
+    pub fn add_game(&mut self, game: &Game) -> Result<(), FenError> {
+        let mut board = game.start(self.magic.clone(), self.attacks.clone())?;
+
+        for &m in &game.moves {
+            if game_ply(&board) >= self.max_ply {
+                break;
+            }
+
+            let score = match (game.result, board.turn) {
+                (GameResult::WhiteWin, PieceColor::White) | (GameResult::BlackWin, PieceColor::Black) => 2,
+                (GameResult::Draw, _) => 1,
+                _ => 0
+            };
+
+            *self.scores.entry((board.hash, encode_move(m))).or_default() += score;
+
+            board.make_move(m);
+        }
+
+        Ok(())
+    }
+
+    // moves that never scored a point are left out, the weights are scaled down
+    // together when the best one doesn't fit
+    pub fn build(&self) -> Book {
+        let max = self.scores.values().copied().max().unwrap_or(0).max(u16::MAX as u32) as u64;
+
+        let mut entries = self.scores.iter()
+            .filter(|(_, &score)| score > 0)
+            .map(|(&(key, raw_move), &score)| BookEntry {
+                key,
+                raw_move,
+                weight: (score as u64 * u16::MAX as u64 / max).max(1) as u16,
+                learn: 0,
+            })
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|entry| (entry.key, u16::MAX - entry.weight, entry.raw_move));
+
+        Book { entries }
+    }
+}
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use crate::{board::Board, moves::{helper::{get_from, get_promotion, get_to}, Move, Position}, piece::{PieceColor, PieceType}, search::SearchResult};
+use crate::{book::BookError, board::{Board, FenError, Outcome}, options::{OptionError, OptionKind, UciOption}, pgn::{GameResult, PgnError}, moves::{helper::{get_from, get_promotion, get_to}, Move, Position}, piece::{PieceColor, PieceType}, search::{info::SearchInfo, score::{mate_distance, Score}, SearchResult}, uci::PositionError};
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -64,12 +64,16 @@ impl fmt::Display for MoveDisplay {
     }
 }
 
-pub struct MoveList<'a>(pub &'a Vec<Move>);
+pub struct MoveListDisplay<'a>(pub &'a Vec<Move>);
 
-impl fmt::Display for MoveList<'_> {
+impl fmt::Display for MoveListDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for &m in self.0 {
-            write!(f, "{} ", MoveDisplay(m))?;
+        for (i, &m) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", MoveDisplay(m))?;
         }
 
         Ok(())
@@ -105,8 +109,142 @@ pub fn show_mask(mask: u64) {
     }
 }
 
+pub struct ScoreDisplay(pub Score);
+
+impl fmt::Display for ScoreDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(moves) = mate_distance(self.0) {
+            write!(f, "mate {}", moves)
+        } else {
+            write!(f, "cp {}", self.0)
+        }
+    }
+}
+
 impl fmt::Display for SearchResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "value: {}, moves: {}", self.value, MoveList(&self.moves))
+        write!(f, "value: {}, moves: {}", ScoreDisplay(self.value), MoveListDisplay(&self.moves))
+    }
+}
+
+impl fmt::Display for SearchInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "depth {} seldepth {} multipv {} score {} nodes {} nps {} time {} hashfull {} pv {}",
+            self.depth,
+            self.seldepth,
+            self.multipv,
+            ScoreDisplay(self.score),
+            self.nodes,
+            self.nps,
+            self.time,
+            self.hashfull,
+            MoveListDisplay(&self.pv)
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Checkmate(PieceColor::White) => write!(f, "checkmate, white wins"),
+            Outcome::Checkmate(PieceColor::Black) => write!(f, "checkmate, black wins"),
+            Outcome::Stalemate => write!(f, "draw by stalemate"),
+            Outcome::FiftyMoves => write!(f, "draw by the fifty-move rule"),
+            Outcome::Repetition => write!(f, "draw by threefold repetition"),
+            Outcome::InsufficientMaterial => write!(f, "draw by insufficient material")
+        }
+    }
+}
+
+impl fmt::Display for UciOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "option name {} type ", self.name)?;
+
+        match &self.kind {
+            OptionKind::Spin { default, min, max } => write!(f, "spin default {} min {} max {}", default, min, max),
+            OptionKind::Check { default } => write!(f, "check default {}", default),
+            OptionKind::Combo { default, values } => {
+                write!(f, "combo default {}", default)?;
+
+                for value in values {
+                    write!(f, " var {}", value)?;
+                }
+
+                Ok(())
+            },
+            OptionKind::String { default } => write!(f, "string default {}", default),
+            OptionKind::Button => write!(f, "button")
+        }
+    }
+}
+
+impl fmt::Display for OptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionError::Unknown(name) => write!(f, "unknown option {}", name),
+            OptionError::MissingValue(name) => write!(f, "missing value for option {}", name),
+            OptionError::InvalidValue { name, value } => write!(f, "invalid value {} for option {}", value, name),
+            OptionError::OutOfRange { name, value, min, max } => write!(f, "value {} for option {} is outside of {}..{}", value, name, min, max)
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::FieldCount(count) => write!(f, "invalid fen: expected 4 to 6 fields, found {}", count),
+            FenError::RankCount(count) => write!(f, "invalid fen: expected 8 ranks, found {}", count),
+            FenError::RankLength { rank, length } => write!(f, "invalid fen: rank {} describes {} squares instead of 8", rank, length),
+            FenError::InvalidPiece(c) => write!(f, "invalid fen: expected one of PNBRQK, found {}", c),
+            FenError::KingCount(color) => write!(f, "invalid fen: {} needs exactly one king", if *color == PieceColor::White { "white" } else { "black" }),
+            FenError::SideToMove(turn) => write!(f, "invalid fen: expected w or b as the side to move, found {}", turn),
+            FenError::Castling(castling) => write!(f, "invalid fen: bad castling rights {}", castling),
+            FenError::EnPassant(square) => write!(f, "invalid fen: bad en passant square {}", square),
+            FenError::Clock(clock) => write!(f, "invalid fen: bad move counter {}", clock)
+        }
+    }
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::Fen(e) => write!(f, "{}", e),
+            PositionError::IllegalMove(m) => write!(f, "illegal move {}, the moves after it were ignored", m)
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::WhiteWin => write!(f, "1-0"),
+            GameResult::BlackWin => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Unknown => write!(f, "*")
+        }
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::Tag(line) => write!(f, "invalid pgn: malformed tag pair on line {}", line),
+            PgnError::UnexpectedChar { line, c } => write!(f, "invalid pgn: unexpected {} on line {}", c, line),
+            PgnError::UnexpectedEnd => write!(f, "invalid pgn: unexpected end of the file"),
+            PgnError::Variation(line) => write!(f, "invalid pgn: unmatched ) on line {}", line),
+            PgnError::Fen(e) => write!(f, "{}", e),
+            PgnError::IllegalMove { line, san } => write!(f, "invalid pgn: illegal move {} on line {}", san, line)
+        }
+    }
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::Io(kind) => write!(f, "can't access the book: {}", kind),
+            BookError::Size(size) => write!(f, "invalid book: {} bytes is not a whole number of entries", size)
+        }
     }
 }
\ No newline at end of file
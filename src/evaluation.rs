@@ -1,4 +1,4 @@
-use crate::{bitboard::{A_FILE_INV, H_FILE_INV}, board::Board, piece::{PieceColor, PieceType}, search::values::*};
+use crate::{bitboard::{A_FILE_INV, H_FILE_INV}, board::Board, piece::{PieceColor, PieceType}, search::{score::{Score, MATE_BOUND}, values::*}};
 
 pub struct EvaluationResult {
     pub white: f64,
@@ -19,6 +19,16 @@ impl EvaluationResult {
         self.white - self.black
     }
 
+    pub fn to_score(&self, color: PieceColor) -> Score {
+        let score = ((self.to_value() * CENTIPAWN_FACTOR).round() as Score).clamp(-MATE_BOUND + 1, MATE_BOUND - 1);
+
+        if color == PieceColor::White {
+            score
+        } else {
+            -score
+        }
+    }
+
     pub fn combine(&self, other: Self) -> Self {
         EvaluationResult {
             white: self.white + other.white,
@@ -27,36 +37,47 @@ impl EvaluationResult {
     }
 }
 
-pub fn evaluate(board: &mut Board) -> EvaluationResult {
-    if board.is_checkmate() {
-        return if board.turn == PieceColor::White {
-            EvaluationResult {
-                white: 100000000.0,
-                black: 0.0
-            }
-        } else {
-            EvaluationResult {
-                white: 0.0,
-                black: 100000000.0
-            }
+// multipliers applied to the evaluation terms, 1.0 leaves a term unchanged
+#[derive(Debug, Clone, Copy)]
+pub struct EvaluationWeights {
+    pub material: f64,
+    pub mobility: f64,
+    pub king_safety: f64
+}
+
+impl Default for EvaluationWeights {
+    fn default() -> Self {
+        EvaluationWeights {
+            material: 1.0,
+            mobility: 1.0,
+            king_safety: 1.0
         }
     }
+}
 
-    if board.is_draw() {
-        return EvaluationResult::default();
+impl EvaluationResult {
+    pub fn scale(&self, factor: f64) -> Self {
+        EvaluationResult {
+            white: self.white * factor,
+            black: self.black * factor
+        }
     }
+}
 
+// only the position on the board, checkmate and stalemate are left to the search which
+// generates the moves anyway
+pub fn evaluate(board: &Board, weights: &EvaluationWeights) -> EvaluationResult {
     let material = EvaluationResult {
-        white: board.bb.count_material(PieceColor::White) as f64 * 2.0,
-        black: board.bb.count_material(PieceColor::Black) as f64 * 2.0
-    };
+        white: board.bb.count_material(PieceColor::White) as f64 * MATERIAL_VALUE,
+        black: board.bb.count_material(PieceColor::Black) as f64 * MATERIAL_VALUE
+    }.scale(weights.material);
 
-    let mobility = evaluate_mobility(board);
+    let mobility = evaluate_mobility(board).scale(weights.mobility);
 
     let king_safety = EvaluationResult {
         white: evaluate_king_safety(board, PieceColor::White) * KING_SAFETY_FACTOR,
         black: evaluate_king_safety(board, PieceColor::Black) * KING_SAFETY_FACTOR
-    };
+    }.scale(weights.king_safety);
 
     material.combine(mobility)
             .combine(king_safety)
@@ -136,12 +157,12 @@ pub fn evaluate_king_safety(board: &Board, color: PieceColor) -> f64 {
     } else {
         board.bb.white_attacks
     };
-    let attack_penalty = attacked_neighbors as f64 * ATTACK_PENALTY;
+    let attack_penalty = attacked_neighbors.count_ones() as f64 * ATTACK_PENALTY;
 
     let material = if color == PieceColor::White {
-        board.bb.count_material(PieceColor::Black) - board.bb.black_pieces.count_ones()
+        board.bb.count_material(PieceColor::Black).saturating_sub(board.bb.black_pieces.count_ones())
     } else {
-        board.bb.count_material(PieceColor::White) - board.bb.white_pieces.count_ones()
+        board.bb.count_material(PieceColor::White).saturating_sub(board.bb.white_pieces.count_ones())
     };
 
     let attack_potential = material as f64 * 0.5;
@@ -5,6 +5,10 @@ pub mod bitboard;
 pub mod evaluation;
 pub mod search;
 pub mod uci;
+pub mod options;
+pub mod pgn;
+pub mod zobrist;
+pub mod book;
 
 pub mod display;
 pub mod perft;
\ No newline at end of file
@@ -1,80 +1,179 @@
 use crate::{bitboard::{RANK_1, RANK_8}, board::Board, piece::{Piece, PieceColor, PieceType}};
 
-use super::{helper::{create, to_move_type}, Move};
+use super::{helper::{create, to_move_type}, list::MoveList};
+
+// which moves get generated, promotions count as captures so that the
+// quiet moves are exactly the ones quiescence search never looks at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GenType {
+    All,
+    Captures,
+    Quiets,
+    Evasions,    // only while in check, the king moves and whatever captures or blocks the checker
+    QuietChecks, // quiet moves that give check
+}
 
 impl Board {
-    pub fn get_legal_moves(&mut self) -> Vec<Move> {
-        let mut moves = self.get_pseudo_legal_moves(self.turn);
+    pub fn get_legal_moves(&mut self) -> MoveList {
+        self.get_legal(GenType::All)
+    }
+
+    pub fn get_legal_captures(&mut self) -> MoveList {
+        self.get_legal(GenType::Captures)
+    }
+
+    pub fn get_legal_quiets(&mut self) -> MoveList {
+        self.get_legal(GenType::Quiets)
+    }
+
+    pub fn get_legal_evasions(&mut self) -> MoveList {
+        self.get_legal(GenType::Evasions)
+    }
+
+    pub fn get_legal_quiet_checks(&mut self) -> MoveList {
+        self.get_legal(GenType::QuietChecks)
+    }
+
+    pub fn get_legal(&mut self, gen: GenType) -> MoveList {
+        let mut moves = self.generate(self.turn, gen);
         self.filter_legal_moves(&mut moves);
-        
+
         moves
     }
 
-    pub fn get_pseudo_legal_moves(&mut self, color: PieceColor) -> Vec<Move> {
-        self.bb.white_attacks = 0;
-        self.bb.black_attacks = 0;
+    pub fn get_pseudo_legal_moves(&mut self, color: PieceColor) -> MoveList {
+        self.generate(color, GenType::All)
+    }
 
-        let pieces = if color == PieceColor::White {
-            self.bb.white_pieces
-        } else {
-            self.bb.black_pieces
-        };
+    pub fn generate(&mut self, color: PieceColor, gen: GenType) -> MoveList {
+        let mut moves = MoveList::new();
+        let mut attacks = 0;
 
-        let mut moves: Vec<Move> = Vec::with_capacity(218);
+        let targets = if gen == GenType::Evasions { self.evasion_targets(color) } else { !0 };
 
-        let mut rem = pieces;
+        let mut rem = self.color_pieces(color);
         while rem != 0 {
-            let index = rem.trailing_zeros() as usize;
-            let square = 1u64 << index;
+            let square = 1u64 << rem.trailing_zeros();
 
             if let Some(piece) = self.bb.get_piece_at(square) {
-                let enemy = if piece.color == PieceColor::White {
-                    self.bb.black_pieces
-                } else {
-                    self.bb.white_pieces
-                };
-
-                match piece.piece_type {
-                    PieceType::Pawn => self.add_pawn_moves(piece, square, &mut moves),
-                    PieceType::Knight => {
-                        let mask = self.get_knight_attacks(square, enemy);
-
-                        if piece.color == PieceColor::White {
-                            self.bb.white_attacks |= mask;
-                        } else {
-                            self.bb.black_attacks |= mask;
-                        }
-
-                        self.add_bitboard_moves(mask, enemy, square, &mut moves, piece);
-                    },
-                    PieceType::Bishop => self.add_bishop_moves(piece, square, &mut moves),
-                    PieceType::Rook => self.add_rook_moves(piece, square, &mut moves),
-                    PieceType::Queen => self.add_queen_moves(piece, square, &mut moves),
-                    PieceType::King => self.add_king_moves(piece, square, &mut moves),
-                }
+                let mask = self.piece_attacks(piece, square);
+                attacks |= mask;
+
+                let mask = if piece.piece_type == PieceType::King { mask } else { mask & targets };
+
+                self.add_piece_moves(piece, square, mask, gen, &mut moves);
             }
 
             rem &= rem - 1;
         }
 
+        self.set_attacks(color, attacks);
+
+        if gen == GenType::QuietChecks {
+            moves.retain(|&m| self.gives_check(m));
+        }
+
         moves
     }
 
-    pub(self) fn add_pawn_moves(&mut self, piece: Piece, square: u64, moves: &mut Vec<Move>) {
-        let enemy = if piece.color == PieceColor::White {
-            self.bb.black_pieces
-        } else {
-            self.bb.white_pieces
-        };
+    // the legal moves of the piece on a single square, cheaper than generating everything
+    // when only a few moves that didn't come from the generator need to be checked
+    pub fn get_legal_moves_from(&self, square: u64) -> MoveList {
+        let mut moves = MoveList::new();
+
+        if let Some(piece) = self.bb.get_piece_at(square).filter(|piece| piece.color == self.turn) {
+            let mask = self.piece_attacks(piece, square);
+
+            self.add_piece_moves(piece, square, mask, GenType::All, &mut moves);
+            self.filter_legal_moves(&mut moves);
+        }
+
+        moves
+    }
 
-        let mask = self.get_pawn_attacks(square, enemy, piece);
+    // the squares one side attacks or can move to, the mobility evaluation reads them
+    // and every call to the generator leaves them behind for the side it generated for
+    pub fn update_attacks(&mut self, color: PieceColor) {
+        let mut attacks = 0;
 
-        if piece.color == PieceColor::White {
-            self.bb.white_attacks |= mask;
+        let mut rem = self.color_pieces(color);
+        while rem != 0 {
+            let square = 1u64 << rem.trailing_zeros();
+
+            if let Some(piece) = self.bb.get_piece_at(square) {
+                attacks |= self.piece_attacks(piece, square);
+            }
+
+            rem &= rem - 1;
+        }
+
+        self.set_attacks(color, attacks);
+    }
+
+    // the squares other pieces than the king can move to while in check, nothing
+    // helps against a double check and without a check there is nothing to evade
+    fn evasion_targets(&self, color: PieceColor) -> u64 {
+        let king = if color == PieceColor::White { self.bb.white_king } else { self.bb.black_king };
+        let checkers = self.get_attackers(king, color.opposite());
+
+        if checkers.count_ones() != 1 {
+            return 0;
+        }
+
+        let mut targets = checkers | self.attacks.get_ray(checkers, king);
+
+        // a pawn that just moved two squares can also be taken en passant
+        let passed = if color == PieceColor::White { self.target_square << 8 } else { self.target_square >> 8 };
+
+        if self.target_square != 0 && checkers & passed != 0 {
+            targets |= self.target_square;
+        }
+
+        targets
+    }
+
+    fn set_attacks(&mut self, color: PieceColor, attacks: u64) {
+        if color == PieceColor::White {
+            self.bb.white_attacks = attacks;
+            self.bb.black_attacks = 0;
         } else {
-            self.bb.black_attacks |= mask;
+            self.bb.white_attacks = 0;
+            self.bb.black_attacks = attacks;
+        }
+    }
+
+    // sliders include the squares of their own pieces they defend
+    fn piece_attacks(&self, piece: Piece, square: u64) -> u64 {
+        let enemy = self.color_pieces(piece.color.opposite());
+        let index = square.trailing_zeros() as usize;
+
+        match piece.piece_type {
+            PieceType::Pawn => self.get_pawn_attacks(square, enemy, piece),
+            PieceType::Knight => self.get_knight_attacks(square, enemy),
+            PieceType::Bishop => self.magic.get_bishop_moves(index, self.bb.pieces),
+            PieceType::Rook => self.magic.get_rook_moves(index, self.bb.pieces),
+            PieceType::Queen => self.magic.get_queen_moves(index, self.bb.pieces),
+            PieceType::King => self.get_king_attacks(square, enemy),
         }
+    }
+
+    fn add_piece_moves(&self, piece: Piece, square: u64, mask: u64, gen: GenType, moves: &mut MoveList) {
+        let enemy = self.color_pieces(piece.color.opposite());
+
+        match piece.piece_type {
+            PieceType::Pawn => self.add_pawn_moves(piece, square, mask, enemy, gen, moves),
+            PieceType::King => {
+                self.add_bitboard_moves(mask, enemy, square, gen, moves, piece);
 
+                if matches!(gen, GenType::All | GenType::Quiets | GenType::QuietChecks) {
+                    self.add_castling_moves(piece, square, moves);
+                }
+            },
+            _ => self.add_bitboard_moves(mask & (self.bb.empty | enemy), enemy, square, gen, moves, piece)
+        }
+    }
+
+    fn add_pawn_moves(&self, piece: Piece, square: u64, mask: u64, enemy: u64, gen: GenType, moves: &mut MoveList) {
         let mut rem = mask;
         while rem != 0 {
             let index = rem.trailing_zeros() as usize;
@@ -89,21 +188,27 @@ impl Board {
                 to & RANK_1 != 0
             };
 
-            if is_promotion {
+            let wanted = match gen {
+                GenType::All | GenType::Evasions => true,
+                GenType::Captures => is_capture || is_en_passant || is_promotion,
+                GenType::Quiets | GenType::QuietChecks => !(is_capture || is_en_passant || is_promotion),
+            };
+
+            if wanted && is_promotion {
                 for piece_type in [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight] {
                     moves.push(create(
-                        square, 
-                        to, 
-                        Some(piece_type), 
-                        to_move_type(is_capture, false, is_en_passant), 
-                        piece.piece_type, 
+                        square,
+                        to,
+                        Some(piece_type),
+                        to_move_type(is_capture, false, is_en_passant),
+                        piece.piece_type,
                         piece.color
                     ));
                 }
-            } else {
+            } else if wanted {
                 moves.push(create(
-                    square, 
-                    to, 
+                    square,
+                    to,
                     None,
                     to_move_type(is_capture, false, is_en_passant),
                     piece.piece_type,
@@ -112,26 +217,10 @@ impl Board {
             }
 
             rem &= rem - 1;
-        } 
-    }
-
-    pub(self) fn add_king_moves(&mut self, piece: Piece, square: u64, moves: &mut Vec<Move>) {
-        let enemy = if piece.color == PieceColor::White {
-            self.bb.black_pieces
-        } else {
-            self.bb.white_pieces
-        };
-
-        let mask = self.get_king_attacks(square, enemy);
-
-        if piece.color == PieceColor::White {
-            self.bb.white_attacks |= mask;
-        } else {
-            self.bb.black_attacks |= mask;
         }
+    }
 
-        self.add_bitboard_moves(mask, enemy, square, moves, piece);
-
+    fn add_castling_moves(&self, piece: Piece, square: u64, moves: &mut MoveList) {
         let color = piece.color.opposite();
 
         if self.castling.can_castle_ks(piece.color)
@@ -164,84 +253,20 @@ impl Board {
         }
     }
 
-    pub(self) fn add_bishop_moves(&mut self, piece: Piece, square: u64, moves: &mut Vec<Move>) {
-        let mask = self.magic.get_bishop_moves(square.trailing_zeros() as usize, self.bb.pieces);
-        
-        if piece.color == PieceColor::White {
-            self.bb.white_attacks |= mask;
-        } else {
-            self.bb.black_attacks |= mask;
-        }
-
-        let enemy = if piece.color == PieceColor::White {
-            self.bb.black_pieces
-        } else {
-            self.bb.white_pieces
-        };
-
-        let mask = mask & (self.bb.empty | enemy);
-
-        self.add_sliding_moves(piece, mask, square, moves);
-    }
-
-    pub(self) fn add_rook_moves(&mut self, piece: Piece, square: u64, moves: &mut Vec<Move>) {
-        let mask = self.magic.get_rook_moves(square.trailing_zeros() as usize, self.bb.pieces);
-        
-        if piece.color == PieceColor::White {
-            self.bb.white_attacks |= mask;
-        } else {
-            self.bb.black_attacks |= mask;
-        }
-        
-        let enemy = if piece.color == PieceColor::White {
-            self.bb.black_pieces
-        } else {
-            self.bb.white_pieces
-        };
-
-        let mask = mask & (self.bb.empty | enemy);
-
-        self.add_sliding_moves(piece, mask, square, moves);
-    }
-
-    pub(self) fn add_queen_moves(&mut self, piece: Piece, square: u64, moves: &mut Vec<Move>) {
-        let mask = self.magic.get_queen_moves(square.trailing_zeros() as usize, self.bb.pieces);
-        
-        if piece.color == PieceColor::White {
-            self.bb.white_attacks |= mask;
-        } else {
-            self.bb.black_attacks |= mask;
-        }
-
-        let enemy = if piece.color == PieceColor::White {
-            self.bb.black_pieces
-        } else {
-            self.bb.white_pieces
-        };
-
-        let mask = mask & (self.bb.empty | enemy);
-
-        self.add_sliding_moves(piece, mask, square, moves);
-    }
-
-    pub(self) fn add_sliding_moves(&self, piece: Piece, mask: u64, square: u64, moves: &mut Vec<Move>) {
-        let enemy = if piece.color == PieceColor::White {
-            self.bb.black_pieces
-        } else {
-            self.bb.white_pieces
+    fn add_bitboard_moves(&self, mask: u64, enemy: u64, square: u64, gen: GenType, moves: &mut MoveList, piece: Piece) {
+        let mask = match gen {
+            GenType::All | GenType::Evasions => mask,
+            GenType::Captures => mask & enemy,
+            GenType::Quiets | GenType::QuietChecks => mask & !enemy,
         };
 
-        self.add_bitboard_moves(mask, enemy, square, moves, piece);
-    }
-
-    pub(self) fn add_bitboard_moves(&self, mask: u64, enemy: u64, square: u64, moves: &mut Vec<Move>, piece: Piece) {
         let mut rem = mask;
         while rem != 0 {
             let index = rem.trailing_zeros() as usize;
             let to = 1u64 << index;
 
             let is_capture = to & enemy != 0;
-            
+
             moves.push(create(
                 square,
                 to,
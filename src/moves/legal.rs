@@ -1,9 +1,9 @@
 use crate::{board::Board, piece::{PieceColor, PieceType}};
 
-use super::{helper::{get_from, get_piece_type, get_to, is_en_passant}, Move};
+use super::{helper::{get_from, get_piece_type, get_to, is_en_passant}, list::MoveList};
 
 impl Board {
-    pub fn filter_legal_moves(&self, moves: &mut Vec<Move>) {
+    pub fn filter_legal_moves(&self, moves: &mut MoveList) {
         moves.retain(|m| if get_piece_type(*m) == PieceType::King { !self.is_attacked(get_to(*m), self.turn.opposite()) } else { true });
         
         if self.is_double_checked(self.turn) {
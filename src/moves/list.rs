@@ -0,0 +1,136 @@
+use std::{fmt, ops::{Deref, DerefMut}};
+
+use super::Move;
+
+// no position has more than 218 legal moves, pseudo legal ones included this is plenty
+pub const MAX_MOVES: usize = 256;
+
+// a fixed capacity move list that lives on the stack, so generating moves never allocates,
+// every move has a slot next to it for the score move ordering gives it, pushing a move
+// doesn't reset the slot so a score only means something once it has been set
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    scores: [i32; MAX_MOVES],
+    len: usize,
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList::new()
+    }
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        MoveList {
+            moves: [0; MAX_MOVES],
+            scores: [0; MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, m: Move) {
+        self.moves[self.len] = m;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn score(&self, index: usize) -> i32 {
+        self.scores[index]
+    }
+
+    pub fn set_score(&mut self, index: usize, score: i32) {
+        self.scores[index] = score;
+    }
+
+    // swaps the scores along with the moves, unlike swapping the slice
+    pub fn swap_scored(&mut self, a: usize, b: usize) {
+        self.moves.swap(a, b);
+        self.scores.swap(a, b);
+    }
+
+    // keeps the order of the remaining moves and their scores
+    pub fn retain(&mut self, mut f: impl FnMut(&Move) -> bool) {
+        let mut len = 0;
+
+        for i in 0..self.len {
+            if f(&self.moves[i]) {
+                self.moves[len] = self.moves[i];
+                self.scores[len] = self.scores[i];
+                len += 1;
+            }
+        }
+
+        self.len = len;
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(iter: I) -> Self {
+        let mut list = MoveList::new();
+
+        for m in iter {
+            list.push(m);
+        }
+
+        list
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter { list: self, index: 0 }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct IntoIter {
+    list: MoveList,
+    index: usize,
+}
+
+impl Iterator for IntoIter {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        let m = self.list.get(self.index).copied();
+        self.index += 1;
+
+        m
+    }
+}
\ No newline at end of file
@@ -12,6 +12,12 @@ pub struct Magic {
     pub bishop_attacks: [Vec<u64>; 64]
 }
 
+impl Default for Magic {
+    fn default() -> Self {
+        Magic::new()
+    }
+}
+
 impl Magic {
     pub fn new() -> Self {
         let mut magic =  Magic {
@@ -119,15 +125,14 @@ impl Magic {
             }
         }
 
-        let size = indices.len();
-        let combinations = 1 << size;
+        let combinations = 1 << indices.len();
         let mut bitboards = Vec::with_capacity(combinations);
 
         for i in 0..combinations {
             let mut blocker = 0u64;
-            for j in 0..size {
+            for (j, square) in indices.iter().enumerate() {
                 if i & (1 << j) != 0 {
-                    blocker |= 1u64 << indices[j];
+                    blocker |= 1u64 << square;
                 }
             }
             bitboards.push(blocker);
@@ -137,8 +142,7 @@ impl Magic {
     }
 
     fn gen_rook_attacks(&mut self) {
-        for square in 0..64 {
-            let magic = ROOK_MAGICS[square];
+        for (square, &magic) in ROOK_MAGICS.iter().enumerate() {
             let shift = self.rook_shifts[square];
 
             let bits = 64 - shift;
@@ -166,8 +170,7 @@ impl Magic {
     }
 
     fn gen_bishop_attacks(&mut self) {
-        for square in 0..64 {
-            let magic = BISHOP_MAGICS[square];
+        for (square, &magic) in BISHOP_MAGICS.iter().enumerate() {
             let shift = self.bishop_shifts[square];
 
             let bits = 64 - shift;
@@ -1,6 +1,6 @@
-use crate::{bitboard::{Bitboard, A1, A8, H1, H8, RANK_2, RANK_4, RANK_5, RANK_7}, board::{Board, Castling}, piece::{Piece, PieceColor, PieceType}};
+use crate::{bitboard::{Bitboard, A1, A8, H1, H8, RANK_2, RANK_4, RANK_5, RANK_7}, board::{Board, Castling}, piece::{Piece, PieceColor, PieceType}, zobrist};
 
-use super::{helper::{get_color, get_from, get_piece_type, get_promotion, get_to, is_capture, is_castling, is_en_passant, is_promotion}, Move, Position};
+use super::{helper::{get_color, get_from, get_piece_type, get_promotion, get_to, is_capture, is_castling, is_en_passant, is_promotion}, Move};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Meta {
@@ -11,24 +11,24 @@ pub struct Meta {
     pub castling: Castling,
 }
 
-pub type State = (Meta, Bitboard, i64);
+pub type State = (Meta, Bitboard, u64);
 
 impl Board {
-    pub fn update_hash(&mut self, m: Move, state: &State) {
+    // en_passant is the square that counted for the hash before the move
+    pub fn update_hash(&mut self, m: Move, state: &State, en_passant: u64) {
         let piece_type = get_piece_type(m);
         let color = get_color(m);
         let from = get_from(m);
         let to = get_to(m);
 
-        let hash_index = Piece::index_from(piece_type, color);
-        let from_pos = Position::from_bitboard(from);
-        let to_pos = Position::from_bitboard(to);
-        self.hash ^= self.hash_table[hash_index * 64 + from_pos.y * 8 + from_pos.x];
-        self.hash ^= self.hash_table[hash_index * 64 + to_pos.y * 8 + to_pos.x];
+        let piece = Piece { color, piece_type };
+        let placed = Piece { color, piece_type: get_promotion(m).unwrap_or(piece_type) };
+
+        self.hash ^= zobrist::piece_key(piece, from);
+        self.hash ^= zobrist::piece_key(placed, to);
 
         if let Some(captured) = state.1.get_piece_at(to) {
-            let pos = Position::from_bitboard(to);
-            self.hash ^= self.hash_table[captured.index() * 64 + pos.y * 8 + pos.x];
+            self.hash ^= zobrist::piece_key(captured, to);
         }
 
         if is_en_passant(m) {
@@ -37,88 +37,30 @@ impl Board {
             } else {
                 to >> 8
             };
-            let pos = Position::from_bitboard(square);
-            
-            self.hash ^= self.hash_table[state.1.get_piece_at(square).unwrap().index() * 64 + pos.y * 8 + pos.x];
+
+            self.hash ^= zobrist::piece_key(state.1.get_piece_at(square).unwrap(), square);
         }
 
         if is_castling(m) {
             let kingside = from << 2 == to;
-            let rook_square = if kingside {
-                to << 1
-            } else {
-                to >> 2
-            };
-
-            let rook_from = Position::from_bitboard(rook_square);
-
-            let rook_to = Position::from_bitboard(if kingside {
-                to >> 1
-            } else {
-                to << 1
-            });
-
-            let rook_index = state.1.get_piece_at(rook_square).unwrap().index();
-
-            self.hash ^= self.hash_table[rook_index * 64 + rook_from.y * 8 + rook_from.x];
-            self.hash ^= self.hash_table[rook_index * 64 + rook_to.y * 8 + rook_to.x];
-
-            match to {
-                A1 => self.hash ^= self.hash_table[12 * 64 + 1],
-                H1 => self.hash ^= self.hash_table[12 * 64],
-                A8 => self.hash ^= self.hash_table[12 * 64 + 3],
-                H8 => self.hash ^= self.hash_table[12 * 64 + 2],
-                _ => {}
-            }
-        }
-
-        if piece_type == PieceType::King {
-            if color == PieceColor::White {
-                self.hash ^= self.hash_table[12 * 64];
-                self.hash ^= self.hash_table[12 * 64 + 1];
-            } else {
-                self.hash ^= self.hash_table[12 * 64 + 2];
-                self.hash ^= self.hash_table[12 * 64 + 3];
-            }
-        }
-
-        if piece_type == PieceType::Rook {
-            if color == PieceColor::White {
-                match from {
-                    A1 => self.hash ^= self.hash_table[12 * 64 + 1],
-                    H1 => self.hash ^= self.hash_table[12 * 64],
-                    _ => {}
-                }
-            } else if color == PieceColor::Black {
-                match from {
-                    A8 => self.hash ^= self.hash_table[12 * 64 + 3],
-                    H8 => self.hash ^= self.hash_table[12 * 64 + 2],
-                    _ => {}
-                }
-            }
-        }
-
-        if state.0.target_square != 0 {
-            let pos = Position::from_bitboard(state.0.target_square);
-            self.hash ^= self.hash_table[12 * 64 + 5 + pos.x];
-        }
-
-        if self.target_square != 0 {
-            let pos = Position::from_bitboard(self.target_square);
-            self.hash ^= self.hash_table[12 * 64 + 5 + pos.x];
-        }
+            let rook_from = if kingside { to << 1 } else { to >> 2 };
+            let rook_to = if kingside { to >> 1 } else { to << 1 };
+            let rook = Piece { color, piece_type: PieceType::Rook };
 
-        if is_promotion(m) {
-            self.hash ^= self.hash_table[hash_index * 64 + to_pos.y * 8 + to_pos.x];
-            let new_index = Piece::index_from(get_promotion(m).unwrap(), color);
-            self.hash ^= self.hash_table[new_index * 64 + to_pos.y * 8 + to_pos.x];
+            self.hash ^= zobrist::piece_key(rook, rook_from);
+            self.hash ^= zobrist::piece_key(rook, rook_to);
         }
 
-        self.hash ^= self.hash_table[12 * 64 + 4];
-        self.hash ^= self.hash_table[12 * 64 + 5];
+        self.hash ^= zobrist::castling_key(&state.0.castling) ^ zobrist::castling_key(&self.castling);
+        self.hash ^= zobrist::en_passant_key(en_passant) ^ zobrist::en_passant_key(self.en_passant_square());
+        self.hash ^= zobrist::turn_key(PieceColor::White);
     }
 
     pub fn make_move(&mut self, m: Move) -> State {
+        self.history.push(self.hash);
+
+        let en_passant = self.en_passant_square();
+
         let meta = Meta {
             turn: self.turn,
             moves: self.moves,
@@ -233,7 +175,7 @@ impl Board {
 
         let state = (meta, bb, self.hash);
 
-        self.update_hash(m, &state);
+        self.update_hash(m, &state, en_passant);
 
         state
     }
@@ -250,9 +192,13 @@ impl Board {
         self.bb.clone_from(bb);
 
         self.hash.clone_from(hash);
+
+        self.history.pop();
     }
 
-    pub fn make_null_move(&mut self) -> (Meta, i64) {
+    pub fn make_null_move(&mut self) -> (Meta, u64) {
+        self.history.push(self.hash);
+
         let meta = Meta {
             turn: self.turn,
             moves: self.moves,
@@ -262,27 +208,28 @@ impl Board {
         };
 
         let hash = self.hash;
-        
-        self.turn = self.turn.opposite();
 
-        if self.target_square != 0 {
-            let pos = Position::from_bitboard(self.target_square);
-            self.hash ^= self.hash_table[12 * 64 + 5 + pos.x];
-            self.target_square = 0;
-        }
+        // whether the square counts depends on the pawns of the side to move
+        self.hash ^= zobrist::en_passant_key(self.en_passant_square());
+        self.hash ^= zobrist::turn_key(PieceColor::White);
+        self.target_square = 0;
 
-        self.hash ^= self.hash_table[12 * 64 + 4];
-        self.hash ^= self.hash_table[12 * 64 + 5];
+        self.turn = self.turn.opposite();
+        // positions before a null move can't be repeated after it
+        self.halfmove_clock = 0;
 
         (meta, hash)
     }
 
-    pub fn unmake_null_move(&mut self, state: &(Meta, i64)) {
+    pub fn unmake_null_move(&mut self, state: &(Meta, u64)) {
         let (meta, hash) = state;
 
         self.turn = meta.turn;
+        self.halfmove_clock = meta.halfmove_clock;
         self.target_square = meta.target_square;
 
         self.hash.clone_from(hash);
+
+        self.history.pop();
     }
 }
\ No newline at end of file
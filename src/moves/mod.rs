@@ -7,6 +7,9 @@ pub mod make;
 pub mod util;
 pub mod helper;
 pub mod attacks;
+pub mod see;
+pub mod list;
+pub mod san;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Position {
@@ -0,0 +1,160 @@
+use crate::{board::Board, piece::PieceType};
+
+use super::{helper::{get_from, get_piece_type, get_promotion, get_to, is_capture, is_castling, is_en_passant}, Move, Position};
+
+impl Board {
+    // standard algebraic notation for a legal move in the current position
+    pub fn to_san(&mut self, m: Move) -> String {
+        let from = get_from(m);
+        let to = get_to(m);
+
+        let mut san = if is_castling(m) {
+            String::from(if to > from { "O-O" } else { "O-O-O" })
+        } else {
+            let piece_type = get_piece_type(m);
+            let from_pos = Position::from_bitboard(from);
+            let capture = is_capture(m) || is_en_passant(m);
+
+            let mut san = String::new();
+
+            if piece_type == PieceType::Pawn {
+                if capture {
+                    san.push(file_char(from_pos.x));
+                }
+            } else {
+                san.push(piece_type.to_char().to_ascii_uppercase());
+
+                // the other pieces of the same kind that could go to the same square
+                let others = self.get_legal_moves().into_iter()
+                    .filter(|&other| get_piece_type(other) == piece_type && get_to(other) == to && get_from(other) != from)
+                    .map(|other| Position::from_bitboard(get_from(other)))
+                    .collect::<Vec<_>>();
+
+                if !others.is_empty() {
+                    if others.iter().all(|pos| pos.x != from_pos.x) {
+                        san.push(file_char(from_pos.x));
+                    } else if others.iter().all(|pos| pos.y != from_pos.y) {
+                        san.push(rank_char(from_pos.y));
+                    } else {
+                        san.push(file_char(from_pos.x));
+                        san.push(rank_char(from_pos.y));
+                    }
+                }
+            }
+
+            if capture {
+                san.push('x');
+            }
+
+            san.push_str(&Position::from_bitboard(to).to_string());
+
+            if let Some(promotion) = get_promotion(m) {
+                san.push('=');
+                san.push(promotion.to_char().to_ascii_uppercase());
+            }
+
+            san
+        };
+
+        let state = self.make_move(m);
+
+        if self.is_checked(self.turn) {
+            san.push(if self.get_legal_moves().is_empty() { '#' } else { '+' });
+        }
+
+        self.unmake_move(&state);
+
+        san
+    }
+
+    // finds the legal move a SAN string stands for, the check marks and annotations are
+    // ignored and so are a missing capture mark, zeros for castling and a missing '='
+    pub fn parse_san(&mut self, string: &str) -> Option<Move> {
+        let san = string.trim().trim_end_matches(['+', '#', '!', '?']);
+        let moves = self.get_legal_moves();
+
+        if let Some(long) = match san.replace('0', "O").as_str() {
+            "O-O" => Some(false),
+            "O-O-O" => Some(true),
+            _ => None
+        } {
+            return moves.into_iter().find(|&m| is_castling(m) && (get_to(m) < get_from(m)) == long);
+        }
+
+        let mut chars = san.chars().filter(|&c| c != 'x' && c != '-' && c != ':').collect::<Vec<_>>();
+
+        // the promotion comes last, with or without the '='
+        let mut promotion = None;
+
+        if let [.., before, last] = chars[..] {
+            if last.is_ascii_alphabetic() && (before.is_ascii_digit() || before == '=') {
+                promotion = Some(PieceType::from_char(last).filter(|&p| p != PieceType::Pawn && p != PieceType::King)?);
+                chars.pop();
+
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+            }
+        }
+
+        // piece letters are upper case, so a 'b' is always the b file
+        let piece_type = match chars.first() {
+            Some(&c) if c.is_ascii_uppercase() => {
+                chars.remove(0);
+                PieceType::from_char(c)?
+            },
+            _ => PieceType::Pawn
+        };
+
+        if chars.len() < 2 {
+            return None;
+        }
+
+        let to = parse_square(chars[chars.len() - 2], chars[chars.len() - 1])?;
+
+        // whatever is left is the file and the rank the piece comes from
+        let mut file = None;
+        let mut rank = None;
+
+        for &c in &chars[..chars.len() - 2] {
+            match c {
+                'a'..='h' if file.is_none() => file = Some((c as u8 - b'a') as usize),
+                '1'..='8' if rank.is_none() => rank = Some(8 - (c as u8 - b'0') as usize),
+                _ => return None
+            }
+        }
+
+        let mut found = moves.into_iter().filter(|&m| {
+            let from = Position::from_bitboard(get_from(m));
+
+            !is_castling(m)
+                && get_piece_type(m) == piece_type
+                && get_to(m) == to
+                && get_promotion(m) == promotion
+                && file.is_none_or(|x| x == from.x)
+                && rank.is_none_or(|y| y == from.y)
+        });
+
+        // an ambiguous move doesn't stand for any of them
+        match (found.next(), found.next()) {
+            (Some(m), None) => Some(m),
+            _ => None
+        }
+    }
+}
+
+fn file_char(x: usize) -> char {
+    (b'a' + x as u8) as char
+}
+
+fn rank_char(y: usize) -> char {
+    (b'8' - y as u8) as char
+}
+
+fn parse_square(file: char, rank: char) -> Option<u64> {
+    if !matches!(file, 'a'..='h') || !matches!(rank, '1'..='8') {
+        return None;
+    }
+
+    Some(Position::bitboard((file as u8 - b'a') as usize, 8 - (rank as u8 - b'0') as usize))
+}
\ No newline at end of file
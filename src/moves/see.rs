@@ -0,0 +1,124 @@
+use crate::{board::Board, piece::{PieceColor, PieceType}, search::{score::Score, values::PIECE_VALUES}};
+
+use super::{helper::{get_captured, get_color, get_from, get_piece_type, get_promotion, get_to, is_en_passant}, Move};
+
+// least valuable first, the order in which pieces join an exchange
+const EXCHANGE_ORDER: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King
+];
+
+fn value(piece_type: PieceType) -> Score {
+    PIECE_VALUES[piece_type.index()] as Score
+}
+
+impl Board {
+    // static exchange evaluation, the material balance after both sides keep recapturing
+    // on the target square with their least valuable piece for as long as it pays off
+    pub fn see(&self, m: Move) -> Score {
+        let from = get_from(m);
+        let to = get_to(m);
+        let index = to.trailing_zeros() as usize;
+
+        let mut gain = [0; 32];
+        let mut depth = 0;
+
+        let mut occupied = self.bb.pieces ^ from;
+
+        if is_en_passant(m) {
+            let captured = if get_color(m) == PieceColor::White { to << 8 } else { to >> 8 };
+            occupied ^= captured;
+        }
+
+        gain[0] = get_captured(m, self).map_or(0, |piece| value(piece.piece_type));
+
+        // the piece standing on the square, which is what the next capture wins
+        let mut on_square = match get_promotion(m) {
+            Some(promotion) => {
+                gain[0] += value(promotion) - value(PieceType::Pawn);
+                promotion
+            },
+            None => get_piece_type(m)
+        };
+
+        let mut color = get_color(m).opposite();
+        let mut attackers = self.attackers_to(index, occupied) & occupied;
+
+        loop {
+            let own = attackers & self.color_pieces(color);
+
+            let Some((piece_type, square)) = self.least_valuable(own, color) else {
+                break;
+            };
+
+            // the king may only take when nothing can take it back
+            if piece_type == PieceType::King && attackers & self.color_pieces(color.opposite()) != 0 {
+                break;
+            }
+
+            depth += 1;
+            gain[depth] = value(on_square) - gain[depth - 1];
+
+            // removing the piece may uncover a slider behind it
+            occupied ^= square;
+            attackers |= self.slider_attackers_to(index, occupied);
+            attackers &= occupied;
+
+            on_square = piece_type;
+            color = color.opposite();
+        }
+
+        // every side is free to stop capturing when that is better for it
+        while depth > 0 {
+            gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+            depth -= 1;
+        }
+
+        gain[0]
+    }
+
+    pub fn see_ge(&self, m: Move, threshold: Score) -> bool {
+        self.see(m) >= threshold
+    }
+
+    // attackers of both colors given a custom occupancy
+    fn attackers_to(&self, index: usize, occupied: u64) -> u64 {
+        let bb = &self.bb;
+
+        (self.attacks.pawn_attacks[PieceColor::Black.index()][index] & bb.white_pawns)
+            | (self.attacks.pawn_attacks[PieceColor::White.index()][index] & bb.black_pawns)
+            | (self.attacks.knight_attacks[index] & (bb.white_knights | bb.black_knights))
+            | (self.attacks.king_attacks[index] & (bb.white_king | bb.black_king))
+            | self.slider_attackers_to(index, occupied)
+    }
+
+    fn slider_attackers_to(&self, index: usize, occupied: u64) -> u64 {
+        let bb = &self.bb;
+
+        let diagonal = bb.white_bishops | bb.black_bishops | bb.white_queens | bb.black_queens;
+        let straight = bb.white_rooks | bb.black_rooks | bb.white_queens | bb.black_queens;
+
+        (self.magic.get_bishop_moves(index, occupied) & diagonal)
+            | (self.magic.get_rook_moves(index, occupied) & straight)
+    }
+
+    pub(crate) fn color_pieces(&self, color: PieceColor) -> u64 {
+        if color == PieceColor::White { self.bb.white_pieces } else { self.bb.black_pieces }
+    }
+
+    fn least_valuable(&self, attackers: u64, color: PieceColor) -> Option<(PieceType, u64)> {
+        EXCHANGE_ORDER.iter().find_map(|&piece_type| {
+            let pieces = attackers & self.bb.get_pieces(piece_type, color);
+
+            if pieces != 0 {
+                Some((piece_type, pieces & pieces.wrapping_neg()))
+            } else {
+                None
+            }
+        })
+    }
+}
\ No newline at end of file
@@ -10,6 +10,12 @@ pub struct AttackTables {
     pub line_masks: [[u64; 64]; 64],
 }
 
+impl Default for AttackTables {
+    fn default() -> Self {
+        AttackTables::new()
+    }
+}
+
 impl AttackTables {
     pub fn new() -> Self {
         let mut tables = AttackTables {
@@ -106,7 +112,7 @@ impl AttackTables {
 
         let mut mask = 0;
 
-        while x >= 0 && x <= 7 && y >= 0 && y <= 7 {
+        while (0..=7).contains(&x) && (0..=7).contains(&y) {
             if x == pos2.x as i32 && y == pos2.y as i32 { break; }
             mask |= Position::bitboard(x as usize, y as usize);
 
@@ -127,7 +133,7 @@ impl AttackTables {
 
         let mut x = pos1.x as i32 + dir.x;
         let mut y = pos1.y as i32 + dir.y;
-        while x >= 0 && x <= 7 && y >= 0 && y <= 7 {
+        while (0..=7).contains(&x) && (0..=7).contains(&y) {
             mask |= Position::bitboard(x as usize, y as usize);
             
             x += dir.x;
@@ -136,7 +142,7 @@ impl AttackTables {
 
         let mut x = pos1.x as i32 - dir.x;
         let mut y = pos1.y as i32 - dir.y;
-        while x >= 0 && x <= 7 && y >= 0 && y <= 7 {
+        while (0..=7).contains(&x) && (0..=7).contains(&y) {
             mask |= Position::bitboard(x as usize, y as usize);
 
             x -= dir.x;
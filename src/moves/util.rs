@@ -1,6 +1,6 @@
-use crate::{bitboard::COLOR_MASK, board::Board, piece::{PieceColor, PieceType}};
+use crate::{bitboard::COLOR_MASK, board::{Board, Outcome}, piece::{PieceColor, PieceType}};
 
-use super::{helper::{create, to_move_type}, Move, Position};
+use super::{helper::{create, get_color, get_from, get_piece_type, get_promotion, get_to, is_castling, is_en_passant, to_move_type}, Move, Position};
 
 impl Board {
     pub fn get_attackers(&self, square: u64, color: PieceColor) -> u64 {
@@ -61,6 +61,59 @@ impl Board {
         self.get_attackers(king, color.opposite()).count_ones() >= 2
     }
 
+    // whether a move checks the opponent, worked out without making it
+    pub fn gives_check(&self, m: Move) -> bool {
+        let color = get_color(m);
+        let from = get_from(m);
+        let to = get_to(m);
+
+        let king = if color == PieceColor::White { self.bb.black_king } else { self.bb.white_king };
+        let king_index = king.trailing_zeros() as usize;
+
+        let mut occupied = (self.bb.pieces & !from) | to;
+
+        if is_en_passant(m) {
+            occupied &= !(if color == PieceColor::White { to << 8 } else { to >> 8 });
+        }
+
+        // the rook is the piece that can give check after castling
+        let mut rook = 0;
+
+        if is_castling(m) {
+            let (rook_from, rook_to) = if to < from { (to >> 2, to << 1) } else { (to << 1, to >> 1) };
+
+            occupied = (occupied & !rook_from) | rook_to;
+            rook = rook_to;
+        }
+
+        let index = to.trailing_zeros() as usize;
+
+        let direct = match get_promotion(m).unwrap_or(get_piece_type(m)) {
+            PieceType::Pawn => self.attacks.pawn_attacks[color.index()][index],
+            PieceType::Knight => self.attacks.knight_attacks[index],
+            PieceType::Bishop => self.magic.get_bishop_moves(index, occupied),
+            PieceType::Rook => self.magic.get_rook_moves(index, occupied),
+            PieceType::Queen => self.magic.get_queen_moves(index, occupied),
+            PieceType::King => 0
+        };
+
+        if direct & king != 0 {
+            return true;
+        }
+
+        // discovered checks by the sliders that stayed where they were, including a castling rook
+        let (bishops, rooks) = if color == PieceColor::White {
+            (self.bb.white_bishops | self.bb.white_queens, self.bb.white_rooks | self.bb.white_queens)
+        } else {
+            (self.bb.black_bishops | self.bb.black_queens, self.bb.black_rooks | self.bb.black_queens)
+        };
+
+        let bishops = bishops & occupied & !from;
+        let rooks = ((rooks & !from) | rook) & occupied;
+
+        (self.magic.get_bishop_moves(king_index, occupied) & bishops) | (self.magic.get_rook_moves(king_index, occupied) & rooks) != 0
+    }
+
     pub fn check_insufficient_material(&self) -> bool {
         let no_heavy_pieces = self.bb.count_non_bk() == 0;
         let white_no_minor = (self.bb.white_bishops | self.bb.white_knights) == 0;
@@ -90,13 +143,61 @@ impl Board {
         self.is_checked(self.turn) && self.get_legal_moves().is_empty()
     }
 
+    pub fn is_stalemate(&mut self) -> bool {
+        !self.is_checked(self.turn) && self.get_legal_moves().is_empty()
+    }
+
     pub fn is_draw(&self) -> bool {
         self.check_insufficient_material() // insufficient material
-        || self.halfmove_clock > 100 // 50-move rule
+        || self.halfmove_clock >= 100 // 50-move rule
+        || self.is_repetition(2) // threefold repetition
+    }
+
+    pub fn outcome(&mut self) -> Option<Outcome> {
+        // checkmate and stalemate take precedence over the other draw rules
+        if self.get_legal_moves().is_empty() {
+            return if self.is_checked(self.turn) {
+                Some(Outcome::Checkmate(self.turn.opposite()))
+            } else {
+                Some(Outcome::Stalemate)
+            };
+        }
+
+        if self.check_insufficient_material() {
+            Some(Outcome::InsufficientMaterial)
+        } else if self.halfmove_clock >= 100 {
+            Some(Outcome::FiftyMoves)
+        } else if self.is_repetition(2) {
+            Some(Outcome::Repetition)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_repetition(&self, count: usize) -> bool {
+        let len = self.history.len();
+        // positions before the last irreversible move can't repeat
+        let limit = (self.halfmove_clock as usize).min(len);
+
+        let mut found = 0;
+
+        // the same side is to move only every other ply
+        for i in (2..=limit).step_by(2) {
+            if self.history[len - i] == self.hash {
+                found += 1;
+
+                if found >= count {
+                    return true;
+                }
+            }
+        }
+
+        false
     }
 
+    // a move from the squares it connects, whether it is legal isn't checked
     pub fn parse_uci_string(&self, string: &str) -> Option<Move> {
-        if string.len() < 4 {
+        if !(4..=5).contains(&string.len()) {
             return None;
         }
 
@@ -123,7 +224,7 @@ impl Board {
                 'r' => Some(PieceType::Rook),
                 'b' => Some(PieceType::Bishop),
                 'n' => Some(PieceType::Knight),
-                _ => None
+                _ => return None
             }
         } else { None };
 
@@ -134,8 +235,19 @@ impl Board {
         let captured = self.bb.get_piece_at(to);
 
         let is_castling = piece.piece_type == PieceType::King && (from >> 2 == to || from << 2 == to);
-        let is_en_passant = to & self.target_square != 0;
+        let is_en_passant = piece.piece_type == PieceType::Pawn && to & self.target_square != 0;
 
         Some(create(from, to, promotion, to_move_type(captured.is_some(), is_castling, is_en_passant), piece.piece_type, piece.color))
     }
+
+    // a move in UCI notation, only if it is legal here
+    pub fn parse_legal_move(&self, string: &str) -> Option<Move> {
+        self.parse_uci_string(string).filter(|&m| self.is_legal(m))
+    }
+
+    // for moves that didn't come from the generator, like the ones a GUI sends or
+    // one remembered from another position, a promotion has to name its piece
+    pub fn is_legal(&self, m: Move) -> bool {
+        self.get_legal_moves_from(get_from(m)).contains(&m)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+use crate::search::{time::DEFAULT_MOVE_OVERHEAD, tt::DEFAULT_HASH_SIZE};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionKind {
+    Spin { default: i64, min: i64, max: i64 },
+    Check { default: bool },
+    Combo { default: String, values: Vec<String> },
+    String { default: String },
+    Button,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    Spin(i64),
+    Check(bool),
+    Combo(String),
+    String(String),
+    Button,
+}
+
+#[derive(Debug, Clone)]
+pub struct UciOption {
+    pub name: String,
+    pub kind: OptionKind,
+    pub value: OptionValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionError {
+    Unknown(String),
+    MissingValue(String),
+    InvalidValue { name: String, value: String },
+    OutOfRange { name: String, value: i64, min: i64, max: i64 },
+}
+
+impl std::error::Error for OptionError {}
+
+impl UciOption {
+    pub fn spin(name: &str, default: i64, min: i64, max: i64) -> Self {
+        UciOption {
+            name: name.to_owned(),
+            kind: OptionKind::Spin { default, min, max },
+            value: OptionValue::Spin(default),
+        }
+    }
+
+    pub fn check(name: &str, default: bool) -> Self {
+        UciOption {
+            name: name.to_owned(),
+            kind: OptionKind::Check { default },
+            value: OptionValue::Check(default),
+        }
+    }
+
+    pub fn combo(name: &str, default: &str, values: &[&str]) -> Self {
+        UciOption {
+            name: name.to_owned(),
+            kind: OptionKind::Combo { default: default.to_owned(), values: values.iter().map(|v| v.to_string()).collect() },
+            value: OptionValue::Combo(default.to_owned()),
+        }
+    }
+
+    pub fn string(name: &str, default: &str) -> Self {
+        UciOption {
+            name: name.to_owned(),
+            kind: OptionKind::String { default: default.to_owned() },
+            value: OptionValue::String(default.to_owned()),
+        }
+    }
+
+    pub fn button(name: &str) -> Self {
+        UciOption {
+            name: name.to_owned(),
+            kind: OptionKind::Button,
+            value: OptionValue::Button,
+        }
+    }
+
+    pub fn parse(&self, value: Option<&str>) -> Result<OptionValue, OptionError> {
+        let invalid = |value: &str| OptionError::InvalidValue { name: self.name.clone(), value: value.to_owned() };
+
+        if let OptionKind::Button = self.kind {
+            return Ok(OptionValue::Button);
+        }
+
+        let value = value.ok_or_else(|| OptionError::MissingValue(self.name.clone()))?;
+
+        match &self.kind {
+            OptionKind::Spin { min, max, .. } => {
+                let parsed = value.parse::<i64>().map_err(|_| invalid(value))?;
+
+                if parsed < *min || parsed > *max {
+                    return Err(OptionError::OutOfRange { name: self.name.clone(), value: parsed, min: *min, max: *max });
+                }
+
+                Ok(OptionValue::Spin(parsed))
+            },
+            OptionKind::Check { .. } => match value.to_ascii_lowercase().as_str() {
+                "true" => Ok(OptionValue::Check(true)),
+                "false" => Ok(OptionValue::Check(false)),
+                _ => Err(invalid(value))
+            },
+            OptionKind::Combo { values, .. } => values.iter()
+                .find(|v| v.eq_ignore_ascii_case(value))
+                .map(|v| OptionValue::Combo(v.clone()))
+                .ok_or_else(|| invalid(value)),
+            OptionKind::String { .. } => Ok(OptionValue::String(value.to_owned())),
+            OptionKind::Button => unreachable!()
+        }
+    }
+}
+
+pub struct Options {
+    options: Vec<UciOption>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options::new()
+    }
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Options {
+            options: vec![
+                UciOption::spin("Hash", DEFAULT_HASH_SIZE as i64, 1, 65536),
+                UciOption::spin("Threads", 1, 1, 256),
+                UciOption::button("Clear Hash"),
+                UciOption::spin("Move Overhead", DEFAULT_MOVE_OVERHEAD as i64, 0, 5000),
+                UciOption::spin("MultiPV", 1, 1, 256),
+                UciOption::check("Ponder", false),
+                UciOption::check("UCI_Chess960", false),
+                UciOption::check("OwnBook", false),
+                UciOption::string("BookFile", "<empty>"),
+                UciOption::spin("BookDepth", 20, 0, 1000),
+                UciOption::combo("BookSelection", "Weighted", &["Best", "Weighted"]),
+                UciOption::spin("Material Weight", 100, 0, 400),
+                UciOption::spin("Mobility Weight", 100, 0, 400),
+                UciOption::spin("King Safety Weight", 100, 0, 400),
+                UciOption::check("LMR", true),
+                UciOption::check("FutilityPruning", true),
+                UciOption::check("ReverseFutilityPruning", true),
+                UciOption::check("LateMovePruning", true),
+                UciOption::check("Razoring", true),
+            ]
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UciOption> {
+        self.options.iter()
+    }
+
+    // option names are case insensitive
+    pub fn get(&self, name: &str) -> Option<&UciOption> {
+        self.options.iter().find(|option| option.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn set(&mut self, name: &str, value: Option<&str>) -> Result<&UciOption, OptionError> {
+        let option = self.options.iter_mut()
+            .find(|option| option.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| OptionError::Unknown(name.to_owned()))?;
+
+        option.value = option.parse(value)?;
+
+        Ok(option)
+    }
+
+    pub fn spin(&self, name: &str) -> i64 {
+        match self.get(name).map(|option| &option.value) {
+            Some(OptionValue::Spin(value)) => *value,
+            _ => panic!("{} is not a spin option", name)
+        }
+    }
+
+    pub fn check(&self, name: &str) -> bool {
+        match self.get(name).map(|option| &option.value) {
+            Some(OptionValue::Check(value)) => *value,
+            _ => panic!("{} is not a check option", name)
+        }
+    }
+
+    pub fn string(&self, name: &str) -> &str {
+        match self.get(name).map(|option| &option.value) {
+            Some(OptionValue::String(value)) | Some(OptionValue::Combo(value)) => value,
+            _ => panic!("{} is not a string option", name)
+        }
+    }
+}
\ No newline at end of file
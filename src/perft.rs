@@ -7,7 +7,7 @@ pub fn perft(board: &mut Board, depth: u32) -> usize {
     if depth == 1 { return moves.len(); }
 
     let mut nodes = 0;
-    for m in moves {
+    for &m in &moves {
         let state = board.make_move(m);
         nodes += perft(board, depth - 1);
         board.unmake_move(&state);
@@ -28,7 +28,7 @@ pub fn split_perft(board: &mut Board, depth: u32) -> usize {
     }
 
     let mut total_nodes = 0;
-    for m in moves {
+    for &m in &moves {
         let move_str = format!("{}", MoveDisplay(m));
 
         let state = board.make_move(m);
@@ -41,4 +41,22 @@ pub fn split_perft(board: &mut Board, depth: u32) -> usize {
     }
 
     total_nodes
-}
\ No newline at end of file
+}
+// perft that stops at every position of the tree to let the tests check it, the leaves
+// are made too so the count is the plain perft count
+pub fn walk(board: &mut Board, depth: u32, check: &mut impl FnMut(&mut Board)) -> usize {
+    check(board);
+
+    if depth == 0 { return 1; }
+
+    let moves = board.get_legal_moves();
+
+    let mut nodes = 0;
+    for m in moves {
+        let state = board.make_move(m);
+        nodes += walk(board, depth - 1, check);
+        board.unmake_move(&state);
+    }
+
+    nodes
+}
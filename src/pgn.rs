@@ -0,0 +1,449 @@
+use std::{iter::Peekable, str::Chars, sync::Arc};
+
+use crate::{board::{Board, FenError, Outcome}, moves::{magic::Magic, tables::AttackTables, Move}, piece::PieceColor};
+
+// the tags every exported game starts with, in this order
+pub const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+// export format keeps the movetext lines under 80 characters
+const LINE_LENGTH: usize = 79;
+
+const EN_PASSANT_SUFFIX: &str = "e.p.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameResult {
+    WhiteWin,
+    BlackWin,
+    Draw,
+    Unknown // the game is still going or the result was lost
+}
+
+impl GameResult {
+    pub fn parse(string: &str) -> Option<Self> {
+        match string {
+            "1-0" => Some(GameResult::WhiteWin),
+            "0-1" => Some(GameResult::BlackWin),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None
+        }
+    }
+}
+
+impl From<Outcome> for GameResult {
+    fn from(outcome: Outcome) -> Self {
+        match outcome.winner() {
+            Some(PieceColor::White) => GameResult::WhiteWin,
+            Some(PieceColor::Black) => GameResult::BlackWin,
+            None => GameResult::Draw
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgnError {
+    Tag(usize),                               // a malformed tag pair, on this line
+    UnexpectedChar { line: usize, c: char },
+    UnexpectedEnd,                            // inside a comment or a tag pair
+    Variation(usize),                         // a variation closed that was never opened
+    Fen(FenError),
+    IllegalMove { line: usize, san: String },
+}
+
+impl std::error::Error for PgnError {}
+
+impl From<FenError> for PgnError {
+    fn from(e: FenError) -> Self {
+        PgnError::Fen(e)
+    }
+}
+
+// a game as its tag pairs and the moves of the main line, there is no place for comments,
+// annotation glyphs or variations so MainLineReader drops them and to_pgn never writes any
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<Move>,
+    pub result: GameResult,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    // the seven tag roster with every value unknown
+    pub fn new() -> Self {
+        Game {
+            tags: SEVEN_TAG_ROSTER.iter().map(|&name| (name.to_string(), unknown_value(name).to_string())).collect(),
+            moves: Vec::new(),
+            result: GameResult::Unknown,
+        }
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string()))
+        }
+    }
+
+    // the position before the first move, from the FEN tag if there is one
+    pub fn start(&self, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Result<Board, FenError> {
+        match self.tag("FEN") {
+            Some(fen) => Board::from_fen(fen, magic, attacks),
+            None => Ok(Board::startpos(magic, attacks))
+        }
+    }
+
+    // the position after the last move
+    pub fn replay(&self, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Result<Board, FenError> {
+        let mut board = self.start(magic, attacks)?;
+
+        for &m in &self.moves {
+            board.make_move(m);
+        }
+
+        Ok(board)
+    }
+
+    // export format, the result tag always agrees with the result of the game
+    pub fn to_pgn(&self, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Result<String, FenError> {
+        let mut pgn = String::new();
+
+        for name in SEVEN_TAG_ROSTER {
+            let value = if name == "Result" {
+                self.result.to_string()
+            } else {
+                self.tag(name).unwrap_or(unknown_value(name)).to_string()
+            };
+
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape(&value)));
+        }
+
+        for (name, value) in self.tags.iter().filter(|(name, _)| !SEVEN_TAG_ROSTER.contains(&name.as_str())) {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape(value)));
+        }
+
+        pgn.push('\n');
+
+        let mut board = self.start(magic, attacks)?;
+        let mut tokens = Vec::new();
+
+        for (i, &m) in self.moves.iter().enumerate() {
+            if board.turn == PieceColor::White {
+                tokens.push(format!("{}.", board.moves));
+            } else if i == 0 {
+                tokens.push(format!("{}...", board.moves));
+            }
+
+            tokens.push(board.to_san(m));
+            board.make_move(m);
+        }
+
+        tokens.push(self.result.to_string());
+
+        let mut length = 0;
+
+        for token in tokens {
+            if length > 0 && length + 1 + token.len() > LINE_LENGTH {
+                pgn.push('\n');
+                length = 0;
+            } else if length > 0 {
+                pgn.push(' ');
+                length += 1;
+            }
+
+            pgn.push_str(&token);
+            length += token.len();
+        }
+
+        pgn.push('\n');
+
+        Ok(pgn)
+    }
+}
+
+enum Token {
+    Tag(String, String),
+    Symbol(String), // a move, a move number or a result
+    Open,
+    Close,
+    Dropped         // comments and annotation glyphs
+}
+
+// reads the games of a PGN file one at a time, as their tags and main line only, the
+// comments, annotations and variations are dropped, so writing a game back loses them.
+// A game that can't be read is skipped up to its result so the games after it can
+// still be read
+pub struct MainLineReader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    line_start: bool,
+    pending: Option<Token>,
+    magic: Arc<Magic>,
+    attacks: Arc<AttackTables>,
+}
+
+impl<'a> MainLineReader<'a> {
+    pub fn new(text: &'a str, magic: Arc<Magic>, attacks: Arc<AttackTables>) -> Self {
+        MainLineReader {
+            chars: text.chars().peekable(),
+            line: 1,
+            line_start: true,
+            pending: None,
+            magic,
+            attacks,
+        }
+    }
+
+    fn read_game(&mut self, depth: &mut usize) -> Result<Option<Game>, PgnError> {
+        let mut game = Game { tags: Vec::new(), moves: Vec::new(), result: GameResult::Unknown };
+        let mut board = None;
+        let mut empty = true;
+
+        loop {
+            let Some(token) = self.token()? else {
+                if empty {
+                    return Ok(None);
+                }
+
+                // without a termination marker the result tag is all there is
+                game.result = game.tag("Result").and_then(GameResult::parse).unwrap_or(GameResult::Unknown);
+
+                return Ok(Some(game));
+            };
+
+            match token {
+                Token::Tag(name, value) => {
+                    // the next game started before this one was terminated
+                    if board.is_some() {
+                        self.pending = Some(Token::Tag(name, value));
+                        game.result = game.tag("Result").and_then(GameResult::parse).unwrap_or(GameResult::Unknown);
+
+                        return Ok(Some(game));
+                    }
+
+                    game.tags.push((name, value));
+                    empty = false;
+                },
+                Token::Open => *depth += 1,
+                Token::Close if *depth == 0 => return Err(PgnError::Variation(self.line)),
+                Token::Close => *depth -= 1,
+                Token::Symbol(_) if *depth > 0 => {},
+                Token::Symbol(symbol) => {
+                    if let Some(result) = GameResult::parse(&symbol) {
+                        game.result = result;
+
+                        return Ok(Some(game));
+                    }
+
+                    empty = false;
+
+                    if symbol.chars().all(|c| c.is_ascii_digit()) {
+                        continue;
+                    }
+
+                    if board.is_none() {
+                        board = Some(game.start(self.magic.clone(), self.attacks.clone())?);
+                    }
+
+                    let board = board.as_mut().unwrap();
+
+                    let m = board.parse_san(&symbol)
+                        .ok_or_else(|| PgnError::IllegalMove { line: self.line, san: symbol.clone() })?;
+
+                    board.make_move(m);
+                    game.moves.push(m);
+                },
+                Token::Dropped => {}
+            }
+        }
+    }
+
+    fn skip_game(&mut self, mut depth: usize) {
+        loop {
+            match self.token() {
+                Ok(None) => return,
+                Ok(Some(Token::Open)) => depth += 1,
+                Ok(Some(Token::Close)) => depth = depth.saturating_sub(1),
+                Ok(Some(Token::Symbol(symbol))) if depth == 0 && GameResult::parse(&symbol).is_some() => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        self.line_start = c == '\n';
+
+        if c == '\n' {
+            self.line += 1;
+        }
+
+        Some(c)
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn at_en_passant_suffix(&self) -> bool {
+        let mut chars = self.chars.clone();
+
+        EN_PASSANT_SUFFIX.chars().all(|c| chars.next() == Some(c))
+    }
+
+    fn token(&mut self) -> Result<Option<Token>, PgnError> {
+        if let Some(token) = self.pending.take() {
+            return Ok(Some(token));
+        }
+
+        loop {
+            let Some(&c) = self.chars.peek() else {
+                return Ok(None);
+            };
+
+            // escaped lines and rest of line comments
+            if c == '%' && self.line_start || c == ';' {
+                self.skip_line();
+                continue;
+            }
+
+            if c.is_whitespace() || matches!(c, '.' | '!' | '?') {
+                self.bump();
+                continue;
+            }
+
+            // the en passant suffix of older files, with or without a space before it
+            if self.at_en_passant_suffix() {
+                for _ in EN_PASSANT_SUFFIX.chars() {
+                    self.bump();
+                }
+
+                continue;
+            }
+
+            if c.is_ascii_alphanumeric() || c == '*' {
+                let mut symbol = String::new();
+
+                while let Some(&c) = self.chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || "*+#=:-/!?_".contains(c)) || self.at_en_passant_suffix() {
+                        break;
+                    }
+
+                    symbol.push(c);
+                    self.bump();
+                }
+
+                return Ok(Some(Token::Symbol(symbol)));
+            }
+
+            self.bump();
+
+            return match c {
+                '[' => self.tag().map(Some),
+                '{' => {
+                    while self.bump().ok_or(PgnError::UnexpectedEnd)? != '}' {}
+
+                    Ok(Some(Token::Dropped))
+                },
+                '$' => {
+                    while self.chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                        self.bump();
+                    }
+
+                    Ok(Some(Token::Dropped))
+                },
+                '(' => Ok(Some(Token::Open)),
+                ')' => Ok(Some(Token::Close)),
+                _ => Err(PgnError::UnexpectedChar { line: self.line, c })
+            };
+        }
+    }
+
+    // the rest of a tag pair after the opening bracket
+    fn tag(&mut self) -> Result<Token, PgnError> {
+        self.skip_whitespace();
+
+        let mut name = String::new();
+
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_ascii_alphanumeric() || c == '_') {
+                break;
+            }
+
+            name.push(c);
+            self.bump();
+        }
+
+        self.skip_whitespace();
+
+        if name.is_empty() || self.bump() != Some('"') {
+            return Err(PgnError::Tag(self.line));
+        }
+
+        let mut value = String::new();
+
+        loop {
+            match self.bump().ok_or(PgnError::UnexpectedEnd)? {
+                '"' => break,
+                '\\' => value.push(self.bump().ok_or(PgnError::UnexpectedEnd)?),
+                c => value.push(c)
+            }
+        }
+
+        self.skip_whitespace();
+
+        if self.bump() != Some(']') {
+            return Err(PgnError::Tag(self.line));
+        }
+
+        Ok(Token::Tag(name, value))
+    }
+}
+
+impl Iterator for MainLineReader<'_> {
+    type Item = Result<Game, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut depth = 0;
+
+        match self.read_game(&mut depth) {
+            Ok(game) => game.map(Ok),
+            Err(e) => {
+                self.skip_game(depth);
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn unknown_value(name: &str) -> &'static str {
+    match name {
+        "Date" => "????.??.??",
+        "Result" => "*",
+        _ => "?"
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
\ No newline at end of file
@@ -42,6 +42,31 @@ impl PieceType {
         }
     }
 
+    // either case
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'p' => Some(PieceType::Pawn),
+            'n' => Some(PieceType::Knight),
+            'b' => Some(PieceType::Bishop),
+            'r' => Some(PieceType::Rook),
+            'q' => Some(PieceType::Queen),
+            'k' => Some(PieceType::King),
+            _ => None
+        }
+    }
+
+    // the letter FEN and SAN use for the piece, in lower case
+    pub fn to_char(&self) -> char {
+        match self {
+            PieceType::Pawn => 'p',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Rook => 'r',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k'
+        }
+    }
+
     pub fn value(&self) -> u32 {
         match self {
             PieceType::Pawn => 1,
@@ -65,6 +90,21 @@ impl Piece {
         self.piece_type.index() + if self.color == PieceColor::White { 0 } else { 6 }
     }
 
+    // upper case for white like in a FEN
+    pub fn from_char(c: char) -> Option<Self> {
+        let color = if c.is_ascii_uppercase() { PieceColor::White } else { PieceColor::Black };
+
+        PieceType::from_char(c).map(|piece_type| Piece { color, piece_type })
+    }
+
+    pub fn to_char(&self) -> char {
+        if self.color == PieceColor::White {
+            self.piece_type.to_char().to_ascii_uppercase()
+        } else {
+            self.piece_type.to_char()
+        }
+    }
+
     pub fn index_from(piece_type: PieceType, color: PieceColor) -> usize {
         piece_type.index() + if color == PieceColor::White { 0 } else { 6 }
     }
@@ -0,0 +1,40 @@
+use crate::{display::MoveDisplay, moves::Move};
+
+use super::score::Score;
+
+#[derive(Debug, Clone)]
+pub struct SearchInfo {
+    pub depth: u8,
+    pub seldepth: usize,
+    pub multipv: usize,
+    pub score: Score,
+    pub nodes: usize,
+    pub nps: usize,
+    pub time: u64,
+    pub hashfull: usize,
+    pub pv: Vec<Move>,
+}
+
+pub trait SearchReporter: Send {
+    fn iteration(&mut self, info: &SearchInfo);
+
+    fn current_move(&mut self, _depth: u8, _m: Move, _number: usize) {}
+}
+
+pub struct UciReporter;
+
+impl SearchReporter for UciReporter {
+    fn iteration(&mut self, info: &SearchInfo) {
+        println!("info {}", info);
+    }
+
+    fn current_move(&mut self, depth: u8, m: Move, number: usize) {
+        println!("info depth {} currmove {} currmovenumber {}", depth, MoveDisplay(m), number);
+    }
+}
+
+pub struct SilentReporter;
+
+impl SearchReporter for SilentReporter {
+    fn iteration(&mut self, _info: &SearchInfo) {}
+}
\ No newline at end of file
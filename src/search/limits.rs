@@ -0,0 +1,43 @@
+use crate::moves::Move;
+
+use super::time::TimeManager;
+
+// everything that can end a search besides an explicit stop
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    pub depth: Option<u8>,
+    pub nodes: Option<usize>,
+    pub mate: Option<u32>,         // stop once a mate in this many moves is found
+    pub search_moves: Vec<Move>,   // only these root moves are searched, all of them when empty
+    pub time: TimeManager,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u8) -> Self {
+        SearchLimits {
+            depth: Some(depth),
+            ..Default::default()
+        }
+    }
+
+    pub fn nodes(nodes: usize) -> Self {
+        SearchLimits {
+            nodes: Some(nodes),
+            ..Default::default()
+        }
+    }
+
+    pub fn mate(moves: u32) -> Self {
+        SearchLimits {
+            mate: Some(moves),
+            ..Default::default()
+        }
+    }
+
+    pub fn time(time: TimeManager) -> Self {
+        SearchLimits {
+            time,
+            ..Default::default()
+        }
+    }
+}
\ No newline at end of file
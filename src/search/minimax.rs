@@ -1,47 +1,82 @@
-use core::f64;
-use crate::{board::Board, evaluation::evaluate, moves::{helper::{get_color, get_piece_type, get_to, is_capture}, Move}, piece::Piece};
+use crate::{board::Board, evaluation::evaluate, moves::{helper::{get_color, get_piece_type, get_to, is_capture, is_en_passant, is_promotion}, Move}, piece::Piece};
 
-use super::{Node, NodeType, Search, SearchResult};
+use super::{picker::MovePicker, pruning::late_move_reduction, score::{from_tt, is_mate, mate_in, mated_in, to_tt, Score, DRAW, INFINITY, MAX_PLY}, values::*, Node, NodeType, Search, SearchResult};
 
 impl Search {
-    pub(super) fn alphabeta(&mut self, board: &mut Board, depth: u8, mut alpha: f64, mut beta: f64, maximizer: bool, null_move: bool) -> SearchResult {
-        if self.is_stopping {
+    pub(super) fn alphabeta(&mut self, board: &mut Board, depth: u8, ply: usize, mut alpha: Score, mut beta: Score, null_move: bool) -> SearchResult {
+        if self.is_stopping() {
             return SearchResult {
-                value: 0.0,
+                value: DRAW,
+                moves: vec![]
+            }
+        }
+
+        if ply > 0 {
+            // a single repetition inside the search tree is enough to score the position as a draw
+            if board.is_repetition(1) {
+                return SearchResult {
+                    value: DRAW,
+                    moves: vec![]
+                }
+            }
+
+            // mate distance pruning
+            alpha = alpha.max(mated_in(ply));
+            beta = beta.min(mate_in(ply + 1));
+
+            if alpha >= beta {
+                return SearchResult {
+                    value: alpha,
+                    moves: vec![]
+                }
+            }
+        }
+
+        if depth == 0 || ply >= MAX_PLY {
+            return SearchResult {
+                value: self.quiescence(board, ply, alpha, beta),
                 moves: vec![]
             }
         }
 
         self.nodes += 1;
+        self.seldepth = self.seldepth.max(ply);
+        self.check_limits();
 
-        if board.is_checkmate() || board.is_draw() || depth == 0 {
+        // checkmate and stalemate only show up once the moves are generated, a mate
+        // takes precedence over the 50 move rule but can't happen with the other draws
+        if board.is_draw() && !(board.halfmove_clock >= 100 && board.is_checkmate()) {
             return SearchResult {
-                value: evaluate(board).to_value(),
+                value: DRAW,
                 moves: vec![]
             }
         }
 
         let hash = board.hash;
+        let entry = self.tt.probe(hash);
 
-        if let Some(entry) = self.tt.get(&hash) {
-            if entry.generation == self.current_generation && entry.depth >= depth {
+        if let Some(entry) = entry {
+            if ply > 0 && entry.depth >= depth {
                 self.tt_hits += 1;
+
+                let value = from_tt(entry.value, ply);
+
                 match entry.node_type {
                     NodeType::PV => {
                         return SearchResult {
-                            value: entry.value,
+                            value,
                             moves: entry.best_move.map_or(vec![], |m| vec![m])
                         };
                     },
-                    NodeType::Cut if entry.value >= beta => {
+                    NodeType::Cut if value >= beta => {
                         return SearchResult {
-                            value: entry.value,
+                            value,
                             moves: entry.best_move.map_or(vec![], |m| vec![m])
                         };
                     },
-                    NodeType::All if entry.value <= alpha => {
+                    NodeType::All if value <= alpha => {
                         return SearchResult {
-                            value: entry.value,
+                            value,
                             moves: entry.best_move.map_or(vec![], |m| vec![m])
                         };
                     },
@@ -50,146 +85,186 @@ impl Search {
             }
         }
 
-        let is_pv = alpha != beta - 1.0;
+        let is_pv = beta - alpha > 1;
+        let in_check = board.is_checked(board.turn);
+
+        // the pruning below relies on the static evaluation, which means nothing while in check
+        let static_eval = if !is_pv && !in_check {
+            // the mobility evaluation reads what the generator normally leaves behind
+            board.update_attacks(board.turn);
+            Some(evaluate(board, &self.weights).to_score(board.turn))
+        } else {
+            None
+        };
+
+        // like the null move these assume that having the move is worth something,
+        // which isn't true in zugzwang prone endings with only pawns left
+        if let Some(eval) = static_eval.filter(|_| board.bb.has_non_pawn_material(board.turn)) {
+            // reverse futility pruning: far enough above beta that no move will drop it below
+            if self.pruning.reverse_futility && depth <= REVERSE_FUTILITY_DEPTH && !is_mate(beta)
+                && eval - REVERSE_FUTILITY_MARGIN * depth as Score >= beta {
+                return SearchResult {
+                    value: eval,
+                    moves: vec![]
+                }
+            }
+
+            // razoring: far below alpha, only captures could still save the position
+            if self.pruning.razoring && depth <= RAZORING_DEPTH && eval + RAZORING_MARGIN * (depth as Score) < alpha {
+                let value = self.quiescence(board, ply, alpha - 1, alpha);
 
-        if depth >= 3 && !board.is_checked(board.turn) && !null_move && !is_pv {
+                if value < alpha {
+                    return SearchResult {
+                        value,
+                        moves: vec![]
+                    }
+                }
+            }
+        }
+
+        if depth >= 3 && !in_check && !null_move && !is_pv {
             let r = 2 + depth / 6;
 
             let state = board.make_null_move();
+            self.played[ply] = None;
 
-            let result = self.alphabeta(board, depth.saturating_sub(r), alpha, alpha + 1.0, !maximizer, true);
+            let result = self.alphabeta(board, depth.saturating_sub(r), ply + 1, -beta, -beta + 1, true);
 
             board.unmake_null_move(&state);
 
-            if maximizer && result.value >= beta {
-                return SearchResult {
-                    value: beta,
-                    moves: vec![]
-                }
-            } else if !maximizer && result.value <= alpha {
+            // don't trust mate scores from a null move search
+            if -result.value >= beta {
                 return SearchResult {
-                    value: alpha,
+                    value: if is_mate(result.value) { beta } else { -result.value },
                     moves: vec![]
                 }
             }
         }
 
-        if maximizer {
-            let mut value = f64::NEG_INFINITY;
-            let mut moves = vec![];
+        let mut value = -INFINITY;
+        let mut moves = vec![];
 
-            let mut node_type = NodeType::All;
+        let mut node_type = NodeType::All;
 
-            let legal_moves = self.sort_moves(&board.get_legal_moves(), board, depth);
+        let mut picker = MovePicker::new(entry.and_then(|entry| entry.best_move), self.refutations(depth, ply));
+        let mut legal_moves = 0;
+        let mut searched = 0;
 
-            for (m, _) in legal_moves {
-                let state = board.make_move(m);
+        // futility pruning: quiet moves can't make up the distance to alpha
+        let futile = self.pruning.futility && depth <= FUTILITY_DEPTH
+            && static_eval.is_some_and(|eval| eval + FUTILITY_MARGIN * (depth as Score) <= alpha);
 
-                let result = self.alphabeta(board, depth - 1, alpha, beta, false, false);
+        while let Some(m) = picker.next(self, board) {
+            legal_moves += 1;
 
-                board.unmake_move(&state);
+            if ply == 0 && !self.is_root_move(m) {
+                continue;
+            }
 
-                if result.value > value {
-                    value = result.value;
+            let i = searched;
+            searched += 1;
 
-                    if !result.moves.is_empty() {
-                        let mut new_moves = vec![m];
-                        new_moves.extend(result.moves);
-                        moves = new_moves;
-                    } else {
-                        moves = vec![m];
-                    }
-                }
+            if ply == 0 && self.start.elapsed().as_millis() > CURRMOVE_DELAY {
+                self.reporter.current_move(depth, m, i + 1);
+            }
 
-                if value > alpha {
-                    alpha = value;
-                    node_type = NodeType::PV;
-                }
+            let quiet = !is_capture(m) && !is_en_passant(m) && !is_promotion(m);
 
-                if value >= beta {
-                    node_type = NodeType::Cut;
-                    self.store_killer_move(&m, depth);
-                    self.store_history(&m, depth);
-                    break;
-                }
+            // known before the move is made, so a pruned move never touches the board
+            let gives_check = board.gives_check(m);
 
+            // the first move is always searched, so there is a score to fall back on
+            if i > 0 && quiet && !in_check && !gives_check && !is_mate(value) {
+                let late = self.pruning.late_move && !is_pv && depth <= LATE_MOVE_PRUNING_DEPTH
+                    && i >= LATE_MOVE_PRUNING_BASE + (depth as usize * depth as usize);
+
+                if futile || late {
+                    continue;
+                }
             }
 
-            self.store_tt(hash, Node {
-                depth,
-                node_type,
-                value,
-                best_move: moves.first().copied(),
-                generation: self.current_generation,
-            });
+            let reduction = if self.pruning.lmr && depth >= LMR_MIN_DEPTH && i >= LMR_MIN_MOVES && quiet && !in_check && !gives_check {
+                late_move_reduction(depth, i, self.history_score(m), is_pv)
+            } else {
+                0
+            };
+
+            let state = board.make_move(m);
+            self.played[ply] = Some(m);
+
+            // principal variation search, only the first move is searched with the full window,
+            // the rest just have to be proven worse and are re-searched when that fails
+            let result = if i == 0 {
+                self.alphabeta(board, depth - 1, ply + 1, -beta, -alpha, false)
+            } else {
+                let mut result = self.alphabeta(board, depth - 1 - reduction, ply + 1, -alpha - 1, -alpha, false);
+
+                // a reduced move that beats alpha gets another look at full depth
+                if reduction > 0 && -result.value > alpha {
+                    result = self.alphabeta(board, depth - 1, ply + 1, -alpha - 1, -alpha, false);
+                }
 
-            SearchResult {
-                value,
-                moves
-            }
-        } else {
-            let mut value = f64::INFINITY;
-            let mut moves = vec![];
+                if -result.value > alpha && -result.value < beta {
+                    self.alphabeta(board, depth - 1, ply + 1, -beta, -alpha, false)
+                } else {
+                    result
+                }
+            };
 
-            let mut node_type = NodeType::All;
+            board.unmake_move(&state);
 
-            let legal_moves = self.sort_moves(&board.get_legal_moves(), board, depth);
+            let score = -result.value;
 
-            for (m, _) in legal_moves {
-                let state = board.make_move(m);
+            if score > value {
+                value = score;
 
-                let result = self.alphabeta(board, depth - 1, alpha, beta, true, false);
+                let mut new_moves = vec![m];
+                new_moves.extend(result.moves);
+                moves = new_moves;
+            }
 
-                board.unmake_move(&state);
+            if value > alpha {
+                alpha = value;
+                node_type = NodeType::PV;
+            }
 
-                if result.value < value {
-                    value = result.value;
-                    
-                    if !result.moves.is_empty() {
-                        let mut new_moves = vec![m];
-                        new_moves.extend(result.moves);
-                        moves = new_moves;
-                    } else {
-                        moves = vec![m];
-                    }
-                }
+            if value >= beta {
+                node_type = NodeType::Cut;
+                self.store_killer_move(&m, depth);
+                self.store_counter_move(m, ply);
+                self.store_history(&m, depth);
+                break;
+            }
+        }
 
-                if value < beta {
-                    beta = value;
-                    node_type = NodeType::PV;
-                }
+        if legal_moves == 0 {
+            return SearchResult {
+                value: if in_check { mated_in(ply) } else { DRAW },
+                moves: vec![]
+            }
+        }
 
-                if value <= alpha {
-                    node_type = NodeType::Cut;
-                    self.store_killer_move(&m, depth);
-                    self.store_history(&m, depth);
-                    break;
-                }
+        if self.is_stopping() {
+            return SearchResult {
+                value: DRAW,
+                moves: vec![]
             }
+        }
 
-            self.store_tt(hash, Node {
+        // a root searched without some of its moves doesn't have a real score
+        if ply > 0 || self.excluded_root_moves.is_empty() {
+            self.tt.store(hash, Node {
                 depth,
                 node_type,
-                value,
+                value: to_tt(value, ply),
                 best_move: moves.first().copied(),
-                generation: self.current_generation,
             });
-            
-            SearchResult {
-                value,
-                moves
-            }
         }
-    }
 
-    fn store_tt(&mut self, hash: i64, node: Node) {
-        if let Some(existing) = self.tt.get(&hash) {
-            if existing.generation == node.generation && existing.depth < node.depth {
-                return;
-            }
+        SearchResult {
+            value,
+            moves
         }
-
-        self.tt.insert(hash, node);
     }
 
     fn store_killer_move(&mut self, m: &Move, depth: u8) {
@@ -199,6 +274,43 @@ impl Search {
         }
     }
 
+    // the reply that refuted the previous move last time
+    pub(super) fn counter_move(&self, ply: usize) -> Option<Move> {
+        let previous = self.played[ply.checked_sub(1)?]?;
+
+        let piece_index = Piece::index_from(get_piece_type(previous), get_color(previous));
+        let to = get_to(previous).trailing_zeros() as usize;
+
+        self.counter_moves[piece_index][to]
+    }
+
+    fn store_counter_move(&mut self, m: Move, ply: usize) {
+        let Some(previous) = ply.checked_sub(1).and_then(|ply| self.played[ply]) else {
+            return;
+        };
+
+        if is_capture(m) || is_en_passant(m) || is_promotion(m) {
+            return;
+        }
+
+        let piece_index = Piece::index_from(get_piece_type(previous), get_color(previous));
+        let to = get_to(previous).trailing_zeros() as usize;
+
+        self.counter_moves[piece_index][to] = Some(m);
+    }
+
+    fn is_root_move(&self, m: Move) -> bool {
+        (self.limits.search_moves.is_empty() || self.limits.search_moves.contains(&m))
+            && !self.excluded_root_moves.contains(&m)
+    }
+
+    fn history_score(&self, m: Move) -> i32 {
+        let piece_index = Piece::index_from(get_piece_type(m), get_color(m));
+        let to = get_to(m).trailing_zeros() as usize;
+
+        self.history[piece_index][to]
+    }
+
     fn store_history(&mut self, m: &Move, depth: u8) {
         let m = *m;
 
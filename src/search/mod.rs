@@ -1,20 +1,31 @@
-use core::f64;
-use std::{collections::HashMap, time::Instant};
+use std::{sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc}, thread, time::Instant};
 
-use crate::{board::Board, moves::Move};
+use crate::{board::Board, evaluation::EvaluationWeights, moves::Move};
 
+use self::{info::{SearchInfo, SearchReporter, SilentReporter}, limits::SearchLimits, pruning::Pruning, score::{is_mate, mate_distance, Score, INFINITY, MAX_PLY}, time::{TimeManager, CHECK_INTERVAL}, tt::TranspositionTable, values::{ASPIRATION_MIN_DEPTH, ASPIRATION_WINDOW}};
+
+pub mod info;
+pub mod limits;
 pub mod minimax;
 pub mod order;
+pub mod picker;
+pub mod pruning;
+pub mod quiescence;
+pub mod score;
+pub mod smp;
+pub mod time;
+pub mod tt;
 pub mod values;
 
+#[derive(Debug, Clone, Copy)]
 pub struct Node {
-    depth: u8,
-    node_type: NodeType,
-    value: f64,
-    best_move: Option<Move>,
-    generation: u16,
+    pub depth: u8,
+    pub node_type: NodeType,
+    pub value: Score,
+    pub best_move: Option<Move>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum NodeType {
     PV,  // exact score
     Cut, // beta
@@ -23,98 +34,285 @@ pub enum NodeType {
 
 #[derive(Debug, Clone)]
 pub struct SearchResult {
-    pub value: f64,
+    pub value: Score,
     pub moves: Vec<Move>
 }
 
 pub struct Search {
-    pub is_stopping: bool,
+    pub stopping: Arc<AtomicBool>,
+    pub pondering: Arc<AtomicBool>,
+    limit_reached: bool,
+    pub limits: SearchLimits,
+    pub multi_pv: usize,
+    excluded_root_moves: Vec<Move>,
+    pub threads: usize,
+    id: usize, // 0 for the main thread
+    helper_nodes: Arc<AtomicUsize>,
     pub nodes: usize,
-    scored_moves: Vec<(Move, f64)>,
-    pub tt: HashMap<i64, Node>,
+    pub seldepth: usize,
+    pub reporter: Box<dyn SearchReporter>,
+    start: Instant,
+    pub tt: Arc<TranspositionTable>,
     pub tt_hits: usize,
-    current_generation: u16,
-    killer_moves: [[Option<Move>; 2]; 100],
+    killer_moves: [[Option<Move>; 2]; MAX_PLY],
+    counter_moves: [[Option<Move>; 64]; 12],
+    played: [Option<Move>; MAX_PLY], // the move made at every ply of the current line
     history: [[i32; 64]; 12],
+    pub weights: EvaluationWeights,
+    pub pruning: Pruning,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Search::new()
+    }
 }
 
 impl Search {
     pub fn new() -> Self {
+        Search::with_tt(Arc::new(TranspositionTable::default()))
+    }
+
+    fn with_tt(tt: Arc<TranspositionTable>) -> Self {
         Search { 
-            is_stopping: false,
+            stopping: Arc::new(AtomicBool::new(false)),
+            pondering: Arc::new(AtomicBool::new(false)),
+            limit_reached: false,
+            limits: SearchLimits::default(),
+            multi_pv: 1,
+            excluded_root_moves: vec![],
+            threads: 1,
+            id: 0,
+            helper_nodes: Arc::new(AtomicUsize::new(0)),
             nodes: 0,
-            scored_moves: Vec::with_capacity(0),
-            tt: HashMap::new(),
+            seldepth: 0,
+            reporter: Box::new(SilentReporter),
+            start: Instant::now(),
+            tt,
             tt_hits: 0,
-            current_generation: 0,
-            killer_moves: [[None; 2]; 100],
+            killer_moves: [[None; 2]; MAX_PLY],
+            counter_moves: [[None; 64]; 12],
+            played: [None; MAX_PLY],
             history: [[0; 64]; 12],
+            weights: EvaluationWeights::default(),
+            pruning: Pruning::default(),
+        }
+    }
+
+    pub fn set_hash_size(&mut self, mb: usize) {
+        match Arc::get_mut(&mut self.tt) {
+            Some(tt) => tt.resize(mb),
+            None => self.tt = Arc::new(TranspositionTable::new(mb))
         }
     }
 
     pub fn search(&mut self, board: &mut Board, depth: u8) -> SearchResult {
-        self.is_stopping = false;
-        self.current_generation = self.current_generation.wrapping_add(1);
-        self.alphabeta(board, depth, f64::NEG_INFINITY, f64::INFINITY, true, false)
+        self.limits = SearchLimits::default();
+        self.start_search();
+
+        let result = self.search_depth(board, depth, None);
+
+        if !self.is_stopping() {
+            self.report(depth, 1, &result);
+        }
+
+        result
     }
 
-    pub fn iterative_deepening(&mut self, board: &mut Board, max_depth: u8, time_limit: u64) -> SearchResult {
-        self.is_stopping = false;
-        self.current_generation = self.current_generation.wrapping_add(1);
-        let start = Instant::now();
+    // the stop and ponder flags are owned by whoever controls the search, they're never reset
+    // here so a stop request or ponderhit can't get lost while the search is starting up
+    fn start_search(&mut self) {
+        self.limit_reached = false;
+        self.nodes = 0;
+        self.helper_nodes.store(0, Ordering::Relaxed);
+        self.seldepth = 0;
+        self.start = Instant::now();
+        self.tt.new_search();
+    }
+
+    // aspiration windows, the search starts with a narrow window around the score of the
+    // previous iteration and widens it whenever the result falls outside of it
+    fn search_depth(&mut self, board: &mut Board, depth: u8, previous: Option<Score>) -> SearchResult {
+        let previous = match previous {
+            Some(previous) if depth >= ASPIRATION_MIN_DEPTH && !is_mate(previous) => previous,
+            _ => return self.alphabeta(board, depth, 0, -INFINITY, INFINITY, false)
+        };
+
+        let mut delta = ASPIRATION_WINDOW;
+        let mut alpha = previous - delta;
+        let mut beta = previous + delta;
+
+        loop {
+            let result = self.alphabeta(board, depth, 0, alpha, beta, false);
+
+            if self.is_stopping() {
+                return result;
+            }
+
+            delta *= 2;
+
+            if result.value <= alpha {
+                alpha = (alpha - delta).max(-INFINITY);
+            } else if result.value >= beta {
+                beta = (beta + delta).min(INFINITY);
+            } else {
+                return result;
+            }
+        }
+    }
+
+    fn report(&mut self, depth: u8, multipv: usize, result: &SearchResult) {
+        let time = self.start.elapsed();
+        let nodes = self.total_nodes();
+
+        let info = SearchInfo {
+            depth,
+            seldepth: self.seldepth,
+            multipv,
+            score: result.value,
+            nodes,
+            nps: (nodes as f64 / time.as_secs_f64().max(0.001)) as usize,
+            time: time.as_millis() as u64,
+            hashfull: self.tt.hashfull(),
+            pv: result.moves.clone(),
+        };
+
+        self.reporter.iteration(&info);
+    }
+
+    pub fn go(&mut self, board: &mut Board, limits: SearchLimits) -> SearchResult {
+        self.go_multi_pv(board, limits)
+            .into_iter()
+            .next()
+            .unwrap_or(SearchResult { value: 0, moves: vec![] })
+    }
+
+    // searches the best multi_pv root moves, each line is found by searching the root
+    // again without the moves of the lines before it, results are ordered best first
+    pub fn go_multi_pv(&mut self, board: &mut Board, limits: SearchLimits) -> Vec<SearchResult> {
+        self.limits = limits;
+        self.start_search();
+
+        let root_moves = board.get_legal_moves().into_iter()
+            .filter(|m| self.limits.search_moves.is_empty() || self.limits.search_moves.contains(m))
+            .count();
 
-        let mut best_result = SearchResult { value: 0.0, moves: vec![] };
+        let lines = self.multi_pv.clamp(1, root_moves.max(1));
+        let max_depth = self.limits.depth.unwrap_or(u8::MAX).min(MAX_PLY as u8 - 1);
+
+        thread::scope(|scope| {
+            let helpers = self.spawn_helpers(scope, board, max_depth);
+
+            let results = self.iterate(board, lines, max_depth);
+
+            helpers.store(true, Ordering::Relaxed);
+
+            results
+        })
+    }
+
+    fn iterate(&mut self, board: &mut Board, lines: usize, max_depth: u8) -> Vec<SearchResult> {
+        let mut best_results: Vec<SearchResult> = vec![];
         
         for depth in 1..=max_depth {
-            let start2 = Instant::now();
-            let result = self.search(board, depth);
+            let mut results = Vec::with_capacity(lines);
+            self.seldepth = 0;
+
+            for line in 0..lines {
+                let previous = best_results.get(line).map(|result| result.value);
+                let result = self.search_depth(board, depth, previous);
+
+                if self.is_stopping() {
+                    break;
+                }
+
+                match result.moves.first() {
+                    Some(&m) => self.excluded_root_moves.push(m),
+                    None => break
+                }
+
+                results.push(result);
+            }
+
+            self.excluded_root_moves.clear();
             
-            if self.is_stopping {
+            // nothing to search when the game is already over
+            if self.is_stopping() || results.is_empty() {
                 break;
             }
 
-            best_result = result;
+            results.sort_by_key(|result| -result.value);
 
-            let elapsed = start.elapsed().as_millis() as u64;
-            if elapsed > (time_limit * 3) / 4 {
-                break;
+            for (i, result) in results.iter().enumerate() {
+                self.report(depth, i + 1, result);
             }
 
-            let time = start2.elapsed();
+            best_results = results;
 
-            println!("depth {depth}: {} {:?}, nodes: {} NPS: {:.2}", best_result, time, self.nodes, self.nodes as f64 / time.as_secs_f64());
-            
-            self.nodes = 0;
+            let best = &best_results[0];
+
+            if let Some(mate) = self.limits.mate {
+                if mate_distance(best.value).is_some_and(|distance| distance > 0 && distance as u32 <= mate) {
+                    break;
+                }
+            }
+
+            self.limits.time.update(best);
+
+            // while pondering the clock isn't running yet
+            if !self.is_pondering() && self.limits.time.soft_limit_reached() {
+                break;
+            }
         }
 
-        best_result
+        best_results
+    }
+
+    pub fn iterative_deepening(&mut self, board: &mut Board, max_depth: u8, time: TimeManager) -> SearchResult {
+        self.go(board, SearchLimits {
+            depth: Some(max_depth),
+            ..SearchLimits::time(time)
+        })
     }
 
     pub fn search_infinite(&mut self, board: &mut Board) -> SearchResult {
-        let mut depth = 1;
-        let mut best_result = SearchResult { value: 0.0, moves: vec![] };
+        self.go(board, SearchLimits::default())
+    }
 
-        loop {
-            let start = Instant::now();
-            let result = self.search(board, depth);
-            
-            if self.is_stopping {
-                break;
+    // nodes searched by the main thread and all helpers
+    pub fn total_nodes(&self) -> usize {
+        self.nodes + self.helper_nodes.load(Ordering::Relaxed)
+    }
+
+    // called for every node, aborts the search once the node or hard time limit has been reached
+    fn check_limits(&mut self) {
+        // helpers are stopped by the main thread, they only publish their node count
+        if self.id > 0 {
+            if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+                self.helper_nodes.fetch_add(CHECK_INTERVAL, Ordering::Relaxed);
             }
 
-            best_result = result;
-            let time = start.elapsed();
+            return;
+        }
 
-            println!("depth {depth}: {} {:?}, nodes: {} NPS: {:.2}", best_result, time, self.nodes, self.nodes as f64 / time.as_secs_f64());
+        if self.limits.nodes.is_some_and(|nodes| self.total_nodes() >= nodes) {
+            self.limit_reached = true;
+        }
 
-            depth += 1;
-            self.nodes = 0;
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) && !self.is_pondering() && self.limits.time.hard_limit_reached() {
+            self.limit_reached = true;
         }
+    }
+
+    pub fn stop(&self) {
+        self.stopping.store(true, Ordering::Relaxed);
+    }
 
-        best_result
+    pub fn is_stopping(&self) -> bool {
+        self.limit_reached || self.stopping.load(Ordering::Relaxed)
     }
 
-    pub fn stop(&mut self) {
-        self.is_stopping = true;
+    pub fn is_pondering(&self) -> bool {
+        self.pondering.load(Ordering::Relaxed)
     }
 }
\ No newline at end of file
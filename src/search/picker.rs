@@ -0,0 +1,151 @@
+use crate::{board::Board, moves::{helper::{is_capture, is_en_passant, is_promotion}, list::MoveList, Move}};
+
+use super::Search;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    TtMove,
+    GenerateCaptures,
+    GoodCaptures,
+    Refutations,
+    GenerateQuiets,
+    Quiets,
+    BadCaptures,
+    Done
+}
+
+// hands out the moves of a node one at a time, best first, and only generates the next
+// group once the previous one is used up, so a cutoff by the transposition table move or
+// a capture never pays for generating and ordering the quiet moves
+pub struct MovePicker {
+    stage: Stage,
+    tt_move: Option<Move>,
+    refutations: [Option<Move>; 3], // both killers and the counter move
+    moves: MoveList,
+    bad_captures: MoveList,
+    index: usize,
+}
+
+impl MovePicker {
+    pub fn new(tt_move: Option<Move>, refutations: [Option<Move>; 3]) -> Self {
+        MovePicker {
+            stage: Stage::TtMove,
+            tt_move,
+            refutations,
+            moves: MoveList::new(),
+            bad_captures: MoveList::new(),
+            index: 0,
+        }
+    }
+
+    pub fn next(&mut self, search: &Search, board: &mut Board) -> Option<Move> {
+        loop {
+            match self.stage {
+                Stage::TtMove => {
+                    self.stage = Stage::GenerateCaptures;
+
+                    // the entry may belong to another position with the same index
+                    self.tt_move = self.tt_move.filter(|&m| board.is_legal(m));
+
+                    if self.tt_move.is_some() {
+                        return self.tt_move;
+                    }
+                },
+                Stage::GenerateCaptures => {
+                    self.moves = board.get_legal_captures();
+
+                    for i in 0..self.moves.len() {
+                        self.moves.set_score(i, search.score_capture(self.moves[i], board));
+                    }
+
+                    self.index = 0;
+                    self.stage = Stage::GoodCaptures;
+                },
+                Stage::GoodCaptures => match self.pick() {
+                    Some(m) if Some(m) == self.tt_move => {},
+                    // captures that lose material are tried after the quiet moves
+                    Some(m) if !is_promotion(m) && !board.see_ge(m, 0) => self.bad_captures.push(m),
+                    Some(m) => return Some(m),
+                    None => {
+                        self.index = 0;
+                        self.stage = Stage::Refutations;
+                    }
+                },
+                Stage::Refutations => {
+                    if self.index >= self.refutations.len() {
+                        self.stage = Stage::GenerateQuiets;
+                        continue;
+                    }
+
+                    let index = self.index;
+                    self.index += 1;
+
+                    let Some(m) = self.refutations[index] else {
+                        continue;
+                    };
+
+                    // killers are only known to be quiet moves in some other position
+                    let duplicate = Some(m) == self.tt_move || self.refutations[..index].contains(&Some(m));
+
+                    if !duplicate && is_quiet(m) && board.is_legal(m) {
+                        return Some(m);
+                    }
+
+                    self.refutations[index] = None;
+                },
+                Stage::GenerateQuiets => {
+                    self.moves = board.get_legal_quiets();
+
+                    for i in 0..self.moves.len() {
+                        self.moves.set_score(i, search.score_quiet(self.moves[i], board));
+                    }
+
+                    self.index = 0;
+                    self.stage = Stage::Quiets;
+                },
+                Stage::Quiets => match self.pick() {
+                    Some(m) if Some(m) == self.tt_move || self.refutations.contains(&Some(m)) => {},
+                    Some(m) => return Some(m),
+                    None => {
+                        self.index = 0;
+                        self.stage = Stage::BadCaptures;
+                    }
+                },
+                Stage::BadCaptures => {
+                    let m = self.bad_captures.get(self.index).copied();
+                    self.index += 1;
+
+                    if m.is_none() {
+                        self.stage = Stage::Done;
+                    }
+
+                    return m;
+                },
+                Stage::Done => return None
+            }
+        }
+    }
+
+    // a selection sort that stops early, moves after a cutoff are never sorted
+    fn pick(&mut self) -> Option<Move> {
+        let best = (self.index..self.moves.len())
+            .max_by(|&a, &b| self.moves.score(a).cmp(&self.moves.score(b)))?;
+
+        self.moves.swap_scored(self.index, best);
+        self.index += 1;
+
+        Some(self.moves[self.index - 1])
+    }
+}
+
+impl Search {
+    pub(super) fn refutations(&self, depth: u8, ply: usize) -> [Option<Move>; 3] {
+        let [first, second] = self.killer_moves[depth as usize];
+
+        [first, second, self.counter_move(ply)]
+    }
+}
+
+fn is_quiet(m: Move) -> bool {
+    !is_capture(m) && !is_en_passant(m) && !is_promotion(m)
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use super::values::{LMR_BASE, LMR_DIVISOR, LMR_HISTORY_DIVISOR};
+
+// every technique can be switched off on its own to measure what it's worth
+#[derive(Debug, Clone, Copy)]
+pub struct Pruning {
+    pub lmr: bool,
+    pub futility: bool,
+    pub reverse_futility: bool,
+    pub late_move: bool,
+    pub razoring: bool,
+}
+
+impl Default for Pruning {
+    fn default() -> Self {
+        Pruning {
+            lmr: true,
+            futility: true,
+            reverse_futility: true,
+            late_move: true,
+            razoring: true,
+        }
+    }
+}
+
+// moves sorted late get reduced more, a good history score takes some of the reduction back
+pub fn late_move_reduction(depth: u8, index: usize, history: i32, is_pv: bool) -> u8 {
+    let reduction = LMR_BASE + (depth as f64).ln() * (index as f64).ln() / LMR_DIVISOR;
+
+    let mut reduction = reduction as i32 - history / LMR_HISTORY_DIVISOR;
+
+    if is_pv {
+        reduction -= 1;
+    }
+
+    // always leave at least one ply to search
+    reduction.clamp(0, depth as i32 - 2) as u8
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use crate::{board::Board, evaluation::evaluate, moves::{helper::{get_captured, is_promotion}, Move}};
+
+use super::{order::mvv_lva, score::{mated_in, Score, DRAW, INFINITY}, values::{DELTA_MARGIN, PIECE_VALUES}, Search};
+
+impl Search {
+    pub(super) fn quiescence(&mut self, board: &mut Board, ply: usize, mut alpha: Score, beta: Score) -> Score {
+        if self.is_stopping() {
+            return DRAW;
+        }
+
+        self.nodes += 1;
+        self.seldepth = self.seldepth.max(ply);
+        self.check_limits();
+
+        let in_check = board.is_checked(board.turn);
+
+        // when in check every evasion has to be searched, standing pat is not an option,
+        // otherwise only captures are, a stalemate goes unnoticed here
+        let mut moves = if in_check { board.get_legal_evasions() } else { board.get_legal_captures() };
+
+        if in_check && moves.is_empty() {
+            return mated_in(ply);
+        }
+
+        if board.is_draw() {
+            return DRAW;
+        }
+
+        let stand_pat = if in_check {
+            None
+        } else {
+            Some(evaluate(board, &self.weights).to_score(board.turn))
+        };
+
+        let mut value = stand_pat.unwrap_or(-INFINITY);
+
+        if value >= beta {
+            return value;
+        }
+
+        alpha = alpha.max(value);
+
+        moves.sort_unstable_by(|&a, &b| mvv_lva(b, board).total_cmp(&mvv_lva(a, board)));
+
+        for m in moves {
+            if let Some(stand_pat) = stand_pat {
+                // delta pruning: even winning the captured piece for free can't raise alpha
+                if !is_promotion(m) && stand_pat + capture_value(m, board) + DELTA_MARGIN <= alpha {
+                    continue;
+                }
+
+                // captures that lose material in the exchange aren't worth looking at
+                if !is_promotion(m) && !board.see_ge(m, 0) {
+                    continue;
+                }
+            }
+
+            let state = board.make_move(m);
+
+            let score = -self.quiescence(board, ply + 1, -beta, -alpha);
+
+            board.unmake_move(&state);
+
+            if score > value {
+                value = score;
+            }
+
+            if value > alpha {
+                alpha = value;
+            }
+
+            if value >= beta {
+                break;
+            }
+        }
+
+        value
+    }
+}
+
+fn capture_value(m: Move, board: &Board) -> Score {
+    get_captured(m, board).map_or(0, |captured| PIECE_VALUES[captured.piece_type.index()] as Score)
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+pub type Score = i32;
+
+pub const INFINITY: Score = 32000;
+pub const MATE: Score = 31000;
+pub const DRAW: Score = 0;
+
+pub const MAX_PLY: usize = 128;
+pub const MATE_BOUND: Score = MATE - MAX_PLY as Score;
+
+pub fn mate_in(ply: usize) -> Score {
+    MATE - ply as Score
+}
+
+pub fn mated_in(ply: usize) -> Score {
+    -MATE + ply as Score
+}
+
+pub fn is_mate(score: Score) -> bool {
+    score.abs() >= MATE_BOUND
+}
+
+// full moves until mate, negative when the side to move is getting mated
+pub fn mate_distance(score: Score) -> Option<i32> {
+    if !is_mate(score) {
+        return None;
+    }
+
+    if score > 0 {
+        Some((MATE - score + 1) / 2)
+    } else {
+        Some(-(MATE + score) / 2)
+    }
+}
+
+// mate scores are stored relative to the node instead of the root,
+// so they stay valid when the position is reached at a different ply
+pub fn to_tt(score: Score, ply: usize) -> Score {
+    if score >= MATE_BOUND {
+        score + ply as Score
+    } else if score <= -MATE_BOUND {
+        score - ply as Score
+    } else {
+        score
+    }
+}
+
+pub fn from_tt(score: Score, ply: usize) -> Score {
+    if score >= MATE_BOUND {
+        score - ply as Score
+    } else if score <= -MATE_BOUND {
+        score + ply as Score
+    } else {
+        score
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, thread::Scope};
+
+use crate::board::Board;
+
+use super::{limits::SearchLimits, time::CHECK_INTERVAL, Search};
+
+// lazy smp, helper threads search the same root without any coordination
+// and only contribute through the shared transposition table
+impl Search {
+    // starts threads - 1 helpers, they run until the returned flag is set
+    pub(super) fn spawn_helpers<'scope>(&self, scope: &'scope Scope<'scope, '_>, board: &Board, max_depth: u8) -> Arc<AtomicBool> {
+        let stopping = Arc::new(AtomicBool::new(false));
+
+        for id in 1..self.threads {
+            let mut helper = self.helper(id, stopping.clone());
+            let mut board = board.clone();
+
+            scope.spawn(move || helper.helper_search(&mut board, max_depth));
+        }
+
+        stopping
+    }
+
+    // helpers get their own killer and history tables, everything else is shared with the main thread
+    fn helper(&self, id: usize, stopping: Arc<AtomicBool>) -> Search {
+        let mut helper = Search::with_tt(self.tt.clone());
+
+        helper.id = id;
+        helper.stopping = stopping;
+        helper.helper_nodes = self.helper_nodes.clone();
+        helper.weights = self.weights;
+        helper.pruning = self.pruning;
+        helper.limits = SearchLimits {
+            search_moves: self.limits.search_moves.clone(),
+            ..Default::default()
+        };
+
+        helper
+    }
+
+    fn helper_search(&mut self, board: &mut Board, max_depth: u8) {
+        let mut previous = None;
+
+        // odd helpers search one ply deeper so the threads don't all work on the same depth
+        for depth in (1 + (self.id % 2) as u8)..=max_depth {
+            let result = self.search_depth(board, depth, previous);
+
+            if self.is_stopping() {
+                break;
+            }
+
+            previous = Some(result.value);
+        }
+
+        // the count is published in steps of CHECK_INTERVAL while searching
+        self.helper_nodes.fetch_add(self.nodes % CHECK_INTERVAL, Ordering::Relaxed);
+    }
+}
\ No newline at end of file
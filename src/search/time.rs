@@ -0,0 +1,129 @@
+use std::time::Instant;
+
+use crate::moves::Move;
+
+use super::{score::Score, SearchResult};
+
+pub const DEFAULT_MOVE_OVERHEAD: u64 = 10;
+
+// the clock is only looked at every few nodes, checking it is not free
+pub const CHECK_INTERVAL: usize = 1024;
+
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+const HARD_LIMIT_FACTOR: u64 = 4;
+const PONDER_BONUS_DIVISOR: u64 = 4;
+
+const MIN_SCALE: f64 = 0.5;
+const MAX_SCALE: f64 = 2.5;
+
+#[derive(Debug, Clone)]
+pub struct TimeManager {
+    start: Instant,
+    soft: Option<u64>, // no new iteration is started after this
+    hard: Option<u64>, // the search is aborted after this
+    scale: f64,
+    best_move: Option<Move>,
+    stability: u32,
+    score: Option<Score>,
+}
+
+impl Default for TimeManager {
+    fn default() -> Self {
+        TimeManager::infinite()
+    }
+}
+
+impl TimeManager {
+    fn new(soft: Option<u64>, hard: Option<u64>) -> Self {
+        TimeManager {
+            start: Instant::now(),
+            soft,
+            hard,
+            scale: 1.0,
+            best_move: None,
+            stability: 0,
+            score: None,
+        }
+    }
+
+    pub fn infinite() -> Self {
+        TimeManager::new(None, None)
+    }
+
+    // go movetime, the whole time is used for this move
+    pub fn fixed(time: u64, overhead: u64) -> Self {
+        let time = time.saturating_sub(overhead).max(1);
+
+        TimeManager::new(Some(time), Some(time))
+    }
+
+    // go wtime/btime, the remaining time is spread over the moves until the next time control
+    pub fn clock(remaining: u64, increment: u64, moves_to_go: Option<u32>, overhead: u64) -> Self {
+        let remaining = remaining.saturating_sub(overhead).max(1);
+        let moves_to_go = moves_to_go.map_or(DEFAULT_MOVES_TO_GO, |m| m.max(1) as u64);
+
+        let hard = (remaining / 2).max(1);
+        let soft = (remaining / moves_to_go + increment / 2).min(remaining / 5).max(1);
+
+        TimeManager::new(Some(soft), Some((soft * HARD_LIMIT_FACTOR).min(hard)))
+    }
+
+    // with Ponder on the search goes on during the opponent's time too, so a move on the
+    // clock can take a quarter longer
+    pub fn with_ponder(mut self) -> Self {
+        self.soft = self.soft.map(|soft| soft + soft / PONDER_BONUS_DIVISOR);
+        self
+    }
+
+    pub fn elapsed(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+
+    pub fn soft_limit(&self) -> Option<u64> {
+        self.soft.map(|soft| ((soft as f64 * self.scale) as u64).min(self.hard.unwrap_or(u64::MAX)))
+    }
+
+    pub fn hard_limit(&self) -> Option<u64> {
+        self.hard
+    }
+
+    pub fn soft_limit_reached(&self) -> bool {
+        self.soft_limit().is_some_and(|soft| self.elapsed() >= soft)
+    }
+
+    pub fn hard_limit_reached(&self) -> bool {
+        self.hard.is_some_and(|hard| self.elapsed() >= hard)
+    }
+
+    // called after every completed iteration, a best move that keeps changing or a
+    // falling score buy more time, a stable best move lets the search finish early
+    pub fn update(&mut self, result: &SearchResult) {
+        // a fixed move time is always used up completely
+        if self.soft == self.hard {
+            return;
+        }
+
+        let best_move = result.moves.first().copied();
+
+        if best_move.is_some() && best_move == self.best_move {
+            self.stability += 1;
+        } else {
+            self.stability = 0;
+        }
+
+        let stability_scale = match self.stability {
+            0 => 1.4,
+            1 => 1.2,
+            2 => 1.0,
+            3 => 0.85,
+            _ => 0.7
+        };
+
+        let drop = self.score.map_or(0, |score| (score - result.value).clamp(0, 200));
+        let drop_scale = 1.0 + drop as f64 / 200.0;
+
+        self.scale = (stability_scale * drop_scale).clamp(MIN_SCALE, MAX_SCALE);
+        self.best_move = best_move;
+        self.score = Some(result.value);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,210 @@
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use crate::moves::Move;
+
+use super::{score::Score, Node, NodeType};
+
+pub const DEFAULT_HASH_SIZE: usize = 16;
+
+const BUCKET_ENTRIES: usize = 4;
+const HASHFULL_SAMPLE: usize = 1000;
+
+// 00000000 00000000 0000000000000000 00000000 00 0000000000000000000000
+// unused   gen      value            depth    t  move
+// t - node type, 0 marks an empty entry
+const MOVE_MASK: u64 = 0x3FFFFF;
+const TYPE_SHIFT: u64 = 22;
+const DEPTH_SHIFT: u64 = 24;
+const VALUE_SHIFT: u64 = 32;
+const GENERATION_SHIFT: u64 = 48;
+
+// the key is stored xored with the data, so a torn write from another thread
+// fails verification instead of returning data belonging to a different position
+#[derive(Default)]
+struct Entry {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+#[derive(Default)]
+#[repr(align(64))]
+struct Bucket {
+    entries: [Entry; BUCKET_ENTRIES],
+}
+
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    generation: AtomicU8,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::new(DEFAULT_HASH_SIZE)
+    }
+}
+
+impl TranspositionTable {
+    pub fn new(mb: usize) -> Self {
+        let mut tt = TranspositionTable {
+            buckets: Vec::new(),
+            generation: AtomicU8::new(0),
+        };
+
+        tt.resize(mb);
+
+        tt
+    }
+
+    pub fn resize(&mut self, mb: usize) {
+        let count = (mb * 1024 * 1024 / size_of::<Bucket>()).max(1);
+
+        self.buckets = Vec::with_capacity(count);
+        self.buckets.resize_with(count, Bucket::default);
+    }
+
+    pub fn clear(&self) {
+        for bucket in &self.buckets {
+            for entry in &bucket.entries {
+                entry.key.store(0, Ordering::Relaxed);
+                entry.data.store(0, Ordering::Relaxed);
+            }
+        }
+
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    pub fn new_search(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn generation(&self) -> u8 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    pub fn size(&self) -> usize {
+        self.buckets.len() * BUCKET_ENTRIES
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<Node> {
+        let bucket = self.bucket(hash);
+
+        for entry in &bucket.entries {
+            let data = entry.data.load(Ordering::Relaxed);
+            let key = entry.key.load(Ordering::Relaxed);
+
+            if data != 0 && key ^ data == hash {
+                return Some(unpack(data));
+            }
+        }
+
+        None
+    }
+
+    pub fn store(&self, hash: u64, node: Node) {
+        let bucket = self.bucket(hash);
+        let generation = self.generation();
+
+        let mut replace = &bucket.entries[0];
+        let mut lowest = i32::MAX;
+
+        for entry in &bucket.entries {
+            let data = entry.data.load(Ordering::Relaxed);
+            let key = entry.key.load(Ordering::Relaxed) ^ data;
+
+            if data == 0 {
+                replace = entry;
+                break;
+            }
+
+            if key == hash {
+                let existing = unpack(data);
+                let age = generation.wrapping_sub(data_generation(data));
+
+                // keep deeper results of the current search, unless the new one is exact
+                if age == 0 && existing.depth > node.depth && node.node_type != NodeType::PV {
+                    return;
+                }
+
+                let node = Node {
+                    best_move: node.best_move.or(existing.best_move),
+                    ..node
+                };
+
+                let data = pack(&node, generation);
+                entry.data.store(data, Ordering::Relaxed);
+                entry.key.store(hash ^ data, Ordering::Relaxed);
+                return;
+            }
+
+            // prefer replacing shallow entries left over from older searches
+            let age = generation.wrapping_sub(data_generation(data)) as i32;
+            let priority = unpack(data).depth as i32 - 8 * age;
+
+            if priority < lowest {
+                lowest = priority;
+                replace = entry;
+            }
+        }
+
+        let data = pack(&node, generation);
+        replace.data.store(data, Ordering::Relaxed);
+        replace.key.store(hash ^ data, Ordering::Relaxed);
+    }
+
+    // permille of the sampled entries written during the current search
+    pub fn hashfull(&self) -> usize {
+        let generation = self.generation();
+        let sample = HASHFULL_SAMPLE.min(self.size());
+
+        let used = self.buckets.iter()
+            .flat_map(|bucket| bucket.entries.iter())
+            .take(sample)
+            .filter(|entry| {
+                let data = entry.data.load(Ordering::Relaxed);
+                data != 0 && data_generation(data) == generation
+            })
+            .count();
+
+        used * 1000 / sample
+    }
+
+    fn bucket(&self, hash: u64) -> &Bucket {
+        let index = ((hash as u128 * self.buckets.len() as u128) >> 64) as usize;
+        &self.buckets[index]
+    }
+}
+
+fn pack(node: &Node, generation: u8) -> u64 {
+    let node_type = match node.node_type {
+        NodeType::PV => 1,
+        NodeType::Cut => 2,
+        NodeType::All => 3,
+    };
+
+    (node.best_move.unwrap_or(0) as u64 & MOVE_MASK)
+        | (node_type << TYPE_SHIFT)
+        | ((node.depth as u64) << DEPTH_SHIFT)
+        | (((node.value as i16) as u16 as u64) << VALUE_SHIFT)
+        | ((generation as u64) << GENERATION_SHIFT)
+}
+
+fn unpack(data: u64) -> Node {
+    let best_move = (data & MOVE_MASK) as Move;
+
+    let node_type = match (data >> TYPE_SHIFT) & 0x3 {
+        1 => NodeType::PV,
+        2 => NodeType::Cut,
+        _ => NodeType::All,
+    };
+
+    Node {
+        depth: (data >> DEPTH_SHIFT) as u8,
+        node_type,
+        value: ((data >> VALUE_SHIFT) as u16 as i16) as Score,
+        best_move: if best_move == 0 { None } else { Some(best_move) },
+    }
+}
+
+fn data_generation(data: u64) -> u8 {
+    (data >> GENERATION_SHIFT) as u8
+}
\ No newline at end of file
@@ -1,13 +1,36 @@
-pub const TT_VALUE: f64 = 10000.0;
+use super::score::Score;
+
 pub const PROMOTION_VALUE: f64 = 9000.0;
-pub const KILLER_MOVE_0: f64 = 900.0;
-pub const KILLER_MOVE_1: f64 = 800.0;
-pub const CHECK_VALUE: f64 = 350.0;
 pub const CASTLING_VALUE: f64 = 300.0;
 pub const HISTORY_VALUE: f64 = 100.0;
 
+pub const MATERIAL_VALUE: f64 = 2.0;
 pub const MOBILITY_VALUE: f64 = 0.05;
 
+pub const DELTA_MARGIN: Score = 200;
+
+pub const ASPIRATION_WINDOW: Score = 25;
+pub const ASPIRATION_MIN_DEPTH: u8 = 4;
+
+pub const LMR_MIN_DEPTH: u8 = 3;
+pub const LMR_MIN_MOVES: usize = 3;
+pub const LMR_BASE: f64 = 0.75;
+pub const LMR_DIVISOR: f64 = 2.25;
+pub const LMR_HISTORY_DIVISOR: i32 = 5000;
+
+// margins are per ply of remaining depth
+pub const FUTILITY_DEPTH: u8 = 3;
+pub const FUTILITY_MARGIN: Score = 100;
+pub const REVERSE_FUTILITY_DEPTH: u8 = 6;
+pub const REVERSE_FUTILITY_MARGIN: Score = 80;
+pub const RAZORING_DEPTH: u8 = 2;
+pub const RAZORING_MARGIN: Score = 300;
+pub const LATE_MOVE_PRUNING_DEPTH: u8 = 3;
+pub const LATE_MOVE_PRUNING_BASE: usize = 4;
+
+// milliseconds before the root starts reporting the move being searched
+pub const CURRMOVE_DELAY: u128 = 1000;
+
 pub const PAWN_SHIELD_VALUE: f64 = 10.0;
 pub const BREATHING_PENALTY: f64 = 0.5;
 pub const PAWN_STORM_PENALTY: f64 = 1.5;
@@ -25,6 +48,9 @@ pub const ROOK_VALUE: f64 = 500.0;
 pub const QUEEN_VALUE: f64 = 900.0;
 pub const KING_VALUE: f64 = 20000.0;
 
+// evaluation units to centipawns
+pub const CENTIPAWN_FACTOR: f64 = PAWN_VALUE / MATERIAL_VALUE;
+
 pub const PIECE_VALUES: [f64; 6] = [
     PAWN_VALUE,
     KNIGHT_VALUE,
@@ -1,12 +1,45 @@
-use std::{io::{self, Write}, sync::Arc};
+use std::{io::{self, Write}, sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}, thread::{self, JoinHandle}, time::Duration};
 
-use crate::{board::Board, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}, piece::PieceColor, search::Search};
+use crate::{board::{Board, FenError, Outcome}, book::{game_ply, Book, BookSelection}, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables, Move}, options::{OptionError, Options, UciOption}, piece::PieceColor, search::{info::UciReporter, limits::SearchLimits, time::TimeManager, Search}};
+
+// used for a plain go without any limits
+const DEFAULT_DEPTH: u8 = 5;
+
+const GO_KEYWORDS: [&str; 12] = [
+    "searchmoves", "ponder", "wtime", "btime", "winc", "binc", "movestogo",
+    "depth", "nodes", "mate", "movetime", "infinite"
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PositionError {
+    Fen(FenError),
+    IllegalMove(String),
+}
+
+impl std::error::Error for PositionError {}
+
+impl From<FenError> for PositionError {
+    fn from(e: FenError) -> Self {
+        PositionError::Fen(e)
+    }
+}
 
 pub struct Uci {
     pub magic: Arc<Magic>,
     pub attacks: Arc<AttackTables>,
     pub board: Board,
-    pub search: Search,
+    pub search: Arc<Mutex<Search>>,
+    pub options: Options,
+    pub book: Option<Book>,
+    stop: Arc<AtomicBool>,
+    ponder: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Default for Uci {
+    fn default() -> Self {
+        Uci::new()
+    }
 }
 
 impl Uci {
@@ -16,11 +49,22 @@ impl Uci {
 
         let board = Board::startpos(magic.clone(), attacks.clone());
 
+        let mut search = Search::new();
+        search.reporter = Box::new(UciReporter);
+
+        let stop = search.stopping.clone();
+        let ponder = search.pondering.clone();
+
         Uci {
             magic,
             attacks,
             board,
-            search: Search::new(),
+            search: Arc::new(Mutex::new(search)),
+            options: Options::new(),
+            book: None,
+            stop,
+            ponder,
+            thread: None,
         }
     }
 
@@ -34,7 +78,10 @@ impl Uci {
 
         loop {
             input.clear();
-            stdin.read_line(&mut input)?;
+            if stdin.read_line(&mut input)? == 0 {
+                break;
+            }
+
             let command = input.trim();
 
             if command == "quit" {
@@ -46,12 +93,19 @@ impl Uci {
             stdout.flush().unwrap();
         }
 
+        self.stop_search();
+
         Ok(())
     }
 
     pub fn identify(&self) {
         println!("id name mchess");
         println!("id author ggod");
+
+        for option in self.options.iter() {
+            println!("{}", option);
+        }
+
         println!("uciok");
     }
 
@@ -63,19 +117,40 @@ impl Uci {
             "uci" => self.identify(),
             "isready" => println!("readyok"),
             "ucinewgame" => {
+                self.stop_search();
                 self.board = Board::startpos(self.magic.clone(), self.attacks.clone());
+                self.search.lock().unwrap().tt.clear();
+            },
+            "position" => {
+                self.stop_search();
+
+                // a fen that can't be parsed leaves the previous position in place,
+                // an illegal move leaves the position before it
+                if let Err(e) = self.handle_position(&tokens[1..]) {
+                    println!("info string {}", e);
+                }
             },
-            "position" => self.handle_position(&tokens[1..]),
-            "go" => self.handle_go(&tokens[1..]),
-            "stop" => self.search.stop(),
+            "go" => {
+                self.stop_search();
+                self.handle_go(&tokens[1..]);
+            },
+            "setoption" => {
+                self.stop_search();
+
+                if let Err(e) = self.handle_setoption(&tokens[1..]) {
+                    println!("info string {}", e);
+                }
+            },
+            "stop" => self.stop_search(),
+            "ponderhit" => self.ponder.store(false, Ordering::Relaxed),
             "quit" => {},
             a => println!("info string unknown option {}", a)
         }
         Ok(())
     }
 
-    pub fn handle_position(&mut self, args: &[&str]) {
-        if args.is_empty() { return; }
+    pub fn handle_position(&mut self, args: &[&str]) -> Result<(), PositionError> {
+        if args.is_empty() { return Ok(()); }
 
         let mut index = 0;
 
@@ -92,21 +167,85 @@ impl Uci {
                 index += 1;
             }
 
-            self.board = Board::from_fen(&fen, self.magic.clone(), self.attacks.clone());
+            self.board = Board::from_fen(&fen, self.magic.clone(), self.attacks.clone())?;
         }
 
         if index < args.len() && args[index] == "moves" {
             index += 1;
             while index < args.len() {
-                self.make_move(args[index]);
+                self.make_move(args[index])?;
                 index += 1;
             }
         }
+
+        Ok(())
+    }
+
+    // setoption name <id> [value <x>], both the name and the value may contain spaces
+    pub fn handle_setoption(&mut self, args: &[&str]) -> Result<(), OptionError> {
+        let value_index = args.iter().position(|&arg| arg == "value");
+
+        let name = args[..value_index.unwrap_or(args.len())].iter()
+            .skip_while(|&&arg| arg == "name")
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let value = value_index.map(|i| args[i + 1..].join(" "));
+
+        let option = self.options.set(&name, value.as_deref())?.clone();
+
+        self.apply_option(&option)
+    }
+
+    fn apply_option(&mut self, option: &UciOption) -> Result<(), OptionError> {
+        let mut search = self.search.lock().unwrap();
+
+        match option.name.as_str() {
+            "Hash" => search.set_hash_size(self.options.spin("Hash") as usize),
+            "Clear Hash" => search.tt.clear(),
+            "Threads" => search.threads = self.options.spin("Threads") as usize,
+            "MultiPV" => search.multi_pv = self.options.spin("MultiPV") as usize,
+            "Material Weight" => search.weights.material = self.options.spin("Material Weight") as f64 / 100.0,
+            "Mobility Weight" => search.weights.mobility = self.options.spin("Mobility Weight") as f64 / 100.0,
+            "King Safety Weight" => search.weights.king_safety = self.options.spin("King Safety Weight") as f64 / 100.0,
+            "LMR" => search.pruning.lmr = self.options.check("LMR"),
+            "FutilityPruning" => search.pruning.futility = self.options.check("FutilityPruning"),
+            "ReverseFutilityPruning" => search.pruning.reverse_futility = self.options.check("ReverseFutilityPruning"),
+            "LateMovePruning" => search.pruning.late_move = self.options.check("LateMovePruning"),
+            "Razoring" => search.pruning.razoring = self.options.check("Razoring"),
+            "BookFile" => {
+                let path = self.options.string("BookFile");
+
+                // a book that can't be read is the same as having none
+                self.book = match path {
+                    "" | "<empty>" => None,
+                    path => Book::open(path).inspect_err(|e| println!("info string {}", e)).ok()
+                };
+            },
+            "UCI_Chess960" if self.options.check("UCI_Chess960") => {
+                self.options.set("UCI_Chess960", Some("false"))?;
+                println!("info string UCI_Chess960 is not supported");
+            },
+            // the remaining options are read when a search starts
+            _ => {}
+        }
+
+        Ok(())
     }
 
     pub fn handle_go(&mut self, args: &[&str]) {
-        let mut time_limit = None;
-        let mut depth = None;
+        if let Some(outcome) = self.board.outcome() {
+            println!("info string {}", outcome);
+
+            // there is nothing to search without legal moves
+            if matches!(outcome, Outcome::Checkmate(_) | Outcome::Stalemate) {
+                println!("bestmove 0000");
+                return;
+            }
+        }
+
+        let mut limits = SearchLimits::default();
 
         let mut wtime = None;
         let mut btime = None;
@@ -116,77 +255,140 @@ impl Uci {
         let mut movetime = None;
 
         let mut infinite = false;
+        let mut ponder = false;
 
         let mut i = 0;
         while i < args.len() {
+            let value = args.get(i + 1).copied().unwrap_or_default();
+
             match args[i] {
-                "movetime" => if let Ok(mt) = args[i + 1].parse::<u64>() {
+                "movetime" => if let Ok(mt) = value.parse::<u64>() {
                     movetime = Some(mt);
                 },
-                "depth" => if let Ok(d) = args[i + 1].parse::<u8>() {
-                    depth = Some(d);
+                "depth" => if let Ok(d) = value.parse::<u8>() {
+                    limits.depth = Some(d);
+                },
+                "nodes" => if let Ok(n) = value.parse::<usize>() {
+                    limits.nodes = Some(n);
                 },
-                "wtime" => if let Ok(t) = args[i + 1].parse::<u64>() {
+                "mate" => if let Ok(m) = value.parse::<u32>() {
+                    limits.mate = Some(m);
+                },
+                "wtime" => if let Ok(t) = value.parse::<u64>() {
                     wtime = Some(t);
                 },
-                "btime" => if let Ok(t) = args[i + 1].parse::<u64>() {
+                "btime" => if let Ok(t) = value.parse::<u64>() {
                     btime = Some(t);
                 },
-                "winc" => if let Ok(inc) = args[i + 1].parse::<u64>() {
+                "winc" => if let Ok(inc) = value.parse::<u64>() {
                     winc = Some(inc);
                 },
-                "binc" => if let Ok(inc) = args[i + 1].parse::<u64>() {
+                "binc" => if let Ok(inc) = value.parse::<u64>() {
                     binc = Some(inc);
                 },
-                "movestogo" => if let Ok(mtg) = args[i + 1].parse::<u32>() {
+                "movestogo" => if let Ok(mtg) = value.parse::<u32>() {
                     movestogo = Some(mtg);
                 },
+                "searchmoves" => {
+                    // the move list runs until the next keyword
+                    while let Some(&arg) = args.get(i + 1).filter(|arg| !GO_KEYWORDS.contains(arg)) {
+                        if let Some(m) = self.board.parse_legal_move(arg) {
+                            limits.search_moves.push(m);
+                        }
+
+                        i += 1;
+                    }
+                },
                 "infinite" => infinite = true,
+                "ponder" => ponder = true,
                 _ => {}
             }
             i += 1;
         }
 
-        if let Some(mt) = movetime {
-            time_limit = Some(mt);
-        } else if wtime.is_some() || btime.is_some() {
-            let is_white = self.board.turn == PieceColor::White;
-            let time = if is_white { wtime } else { btime };
-            let inc = if is_white { winc } else { binc };
-
-            if let Some(remaining) = time {
-                let moves_left = movestogo.unwrap_or(30);
-                let increment = inc.unwrap_or(0);
+        // a search that has to wait for stop or ponderhit can't answer from the book
+        if !infinite && !ponder {
+            let book_move = self.book_move()
+                .filter(|m| limits.search_moves.is_empty() || limits.search_moves.contains(m));
 
-                let base_time = remaining / moves_left as u64;
-                let allocated = base_time + increment / 2;
-
-                time_limit = Some(std::cmp::min(allocated, remaining / 5));
+            if let Some(m) = book_move {
+                println!("bestmove {}", MoveDisplay(m));
+                return;
             }
         }
 
-        let result = if let Some(time) = time_limit {
-            self.search.iterative_deepening(&mut self.board, depth.unwrap_or(u8::MAX), time)
-        } else if infinite {
-            self.search.search_infinite(&mut self.board)
+        let overhead = self.options.spin("Move Overhead") as u64;
+
+        let time = if let Some(mt) = movetime {
+            Some(TimeManager::fixed(mt, overhead))
         } else {
-            self.search.search(&mut self.board, depth.unwrap_or(5))
-        };
+            let (time, inc) = if self.board.turn == PieceColor::White { (wtime, winc) } else { (btime, binc) };
 
-        let best_move = result.moves.first();
+            time.map(|remaining| TimeManager::clock(remaining, inc.unwrap_or(0), movestogo, overhead))
+                .map(|time| if self.options.check("Ponder") { time.with_ponder() } else { time })
+        };
 
-        if let Some(m) = best_move.as_ref() {
-            println!("bestmove {}", MoveDisplay(**m));
-        } else {
-            println!("bestmove 0000");
+        if let Some(time) = time {
+            limits.time = time;
+        } else if !infinite && !ponder && limits.depth.is_none() && limits.nodes.is_none() && limits.mate.is_none() {
+            limits.depth = Some(DEFAULT_DEPTH);
         }
+
+        let search = self.search.clone();
+        let stop = self.stop.clone();
+        let pondering = self.ponder.clone();
+        let mut board = self.board.clone();
+
+        stop.store(false, Ordering::Relaxed);
+        pondering.store(ponder, Ordering::Relaxed);
+
+        self.thread = Some(thread::spawn(move || {
+            let mut search = search.lock().unwrap();
+
+            let result = search.go(&mut board, limits);
+
+            // an infinite or ponder search may only report its move after being told to stop
+            // or, when pondering, after the opponent played the expected move
+            while (infinite || pondering.load(Ordering::Relaxed)) && !stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            // a search stopped right away still has to answer with a legal move
+            let best_move = result.moves.first().copied()
+                .or_else(|| board.get_legal_moves().first().copied());
+
+            match (best_move, result.moves.get(1)) {
+                (Some(m), Some(&ponder)) => println!("bestmove {} ponder {}", MoveDisplay(m), MoveDisplay(ponder)),
+                (Some(m), None) => println!("bestmove {}", MoveDisplay(m)),
+                (None, _) => println!("bestmove 0000")
+            }
+        }));
     }
 
-    pub fn make_move(&mut self, move_str: &str) {
-        let m = self.board.parse_uci_string(move_str);
+    // a move from the book when OwnBook is on and the game is still short enough
+    pub fn book_move(&mut self) -> Option<Move> {
+        if !self.options.check("OwnBook") || game_ply(&self.board) >= self.options.spin("BookDepth") as u32 {
+            return None;
+        }
 
-        if let Some(m) = m {
-            self.board.make_move(m);
+        let selection = BookSelection::parse(self.options.string("BookSelection"))?;
+
+        self.book.as_ref()?.probe(&mut self.board, selection)
+    }
+
+    pub fn stop_search(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.stop.store(true, Ordering::Relaxed);
+            thread.join().unwrap();
         }
     }
+
+    pub fn make_move(&mut self, move_str: &str) -> Result<(), PositionError> {
+        let m = self.board.parse_legal_move(move_str)
+            .ok_or_else(|| PositionError::IllegalMove(move_str.to_string()))?;
+
+        self.board.make_move(m);
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,133 @@
+use std::{io::ErrorKind, sync::Arc};
+
+use mchess::{board::Board, book::{encode_move, game_ply, Book, BookBuilder, BookEntry, BookError, BookSelection}, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}, pgn::MainLineReader, uci::Uci};
+
+const GAMES: &str = "
+1. e4 e5 2. Nf3 Nc6 1-0
+1. e4 c5 2. Nf3 d6 0-1
+1. e4 e5 2. Bc4 Nf6 1/2-1/2
+1. d4 d5 2. c4 e6 1-0
+1. d4 Nf6 2. c4 g6 *
+";
+
+fn build(max_ply: u32) -> Book {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut builder = BookBuilder::new(max_ply, magic.clone(), attacks.clone());
+
+    for game in MainLineReader::new(GAMES, magic, attacks) {
+        builder.add_game(&game.unwrap()).unwrap();
+    }
+
+    builder.build()
+}
+
+fn book_moves(book: &Book, board: &mut Board) -> Vec<(String, u16)> {
+    book.moves(board).into_iter().map(|(m, weight)| (MoveDisplay(m).to_string(), weight)).collect()
+}
+
+#[test]
+fn test_encode_move() {
+    let board = Board::from_fen("4k3/P7/8/8/8/8/4P3/R3K2R w KQ - 0 1", Arc::new(Magic::new()), Arc::new(AttackTables::new())).unwrap();
+    let encode = |m| encode_move(board.parse_legal_move(m).unwrap());
+
+    assert_eq!(encode("e2e4"), 12 << 6 | 28);
+    assert_eq!(encode("a7a8q"), 4 << 12 | 48 << 6 | 56);
+    assert_eq!(encode("a7a8n"), 1 << 12 | 48 << 6 | 56);
+
+    // castling is the king taking its rook
+    assert_eq!(encode("e1g1"), 4 << 6 | 7);
+    assert_eq!(encode("e1c1"), 4 << 6);
+}
+
+#[test]
+fn test_game_ply() {
+    let ply = |fen| game_ply(&Board::from_fen(fen, Arc::new(Magic::new()), Arc::new(AttackTables::new())).unwrap());
+
+    assert_eq!(ply("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), 0);
+    assert_eq!(ply("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"), 3);
+
+    // a fullmove number of 0 reads like the first move
+    assert_eq!(ply("4k3/8/8/8/8/8/8/4K3 w - - 0 0"), 0);
+    assert_eq!(ply("4k3/8/8/8/8/8/8/4K3 b - - 0 0"), 1);
+}
+
+#[test]
+fn test_build() {
+    let book = build(4);
+    let mut board = Board::startpos(Arc::new(Magic::new()), Arc::new(AttackTables::new()));
+
+    // two points for a win and one for a draw, the losing and unfinished games add nothing
+    assert_eq!(book_moves(&book, &mut board), vec![("e2e4".to_string(), 3), ("d2d4".to_string(), 2)]);
+    assert_eq!(book.probe(&mut board, BookSelection::Best).map(|m| MoveDisplay(m).to_string()).as_deref(), Some("e2e4"));
+
+    for _ in 0..20 {
+        let m = book.probe(&mut board, BookSelection::Weighted).unwrap();
+        assert!(["e2e4", "d2d4"].contains(&MoveDisplay(m).to_string().as_str()));
+    }
+
+    board.make_move(board.parse_legal_move("e2e4").unwrap());
+    assert_eq!(book_moves(&book, &mut board), vec![("c7c5".to_string(), 2), ("e7e5".to_string(), 1)]);
+
+    // the black moves of a white win never scored
+    board.make_move(board.parse_legal_move("e7e5").unwrap());
+    board.make_move(board.parse_legal_move("g1f3").unwrap());
+    assert!(book_moves(&book, &mut board).is_empty());
+    assert_eq!(book.probe(&mut board, BookSelection::Weighted), None);
+
+    assert!(book.entries.windows(2).all(|pair| pair[0].key <= pair[1].key));
+
+    // only the first plies of every game
+    assert_eq!(book.entries.len(), 9);
+    assert_eq!(build(2).entries.len(), 4);
+}
+
+#[test]
+fn test_bytes() {
+    let book = build(8);
+    let bytes = book.to_bytes();
+
+    assert_eq!(bytes.len(), book.entries.len() * 16);
+    assert_eq!(Book::from_bytes(&bytes).unwrap().entries, book.entries);
+
+    let entry = BookEntry { key: 0x463b96181691fc9c, raw_move: 12 << 6 | 28, weight: 7, learn: 1 };
+    let bytes = Book { entries: vec![entry] }.to_bytes();
+
+    assert_eq!(bytes, [0x46, 0x3b, 0x96, 0x18, 0x16, 0x91, 0xfc, 0x9c, 0x03, 0x1c, 0x00, 0x07, 0x00, 0x00, 0x00, 0x01]);
+
+    assert_eq!(Book::from_bytes(&bytes[..15]).err(), Some(BookError::Size(15)));
+    assert_eq!(Book::open("no/such/book.bin").err(), Some(BookError::Io(ErrorKind::NotFound)));
+}
+
+#[test]
+fn test_uci() {
+    let path = std::env::temp_dir().join(format!("mchess-book-{}.bin", std::process::id()));
+    build(8).save(&path).unwrap();
+
+    let mut uci = Uci::new();
+
+    uci.command(&format!("setoption name BookFile value {}", path.display())).unwrap();
+    uci.command("setoption name BookSelection value best").unwrap();
+    assert!(uci.book.is_some());
+
+    // the book is only used with OwnBook
+    assert_eq!(uci.book_move(), None);
+
+    uci.command("setoption name OwnBook value true").unwrap();
+    assert_eq!(uci.book_move().map(|m| MoveDisplay(m).to_string()).as_deref(), Some("e2e4"));
+
+    uci.command("position startpos moves e2e4").unwrap();
+    assert_eq!(uci.book_move().map(|m| MoveDisplay(m).to_string()).as_deref(), Some("c7c5"));
+
+    uci.command("setoption name BookDepth value 1").unwrap();
+    assert_eq!(uci.book_move(), None);
+
+    uci.command("position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0").unwrap();
+    assert_eq!(uci.book_move().map(|m| MoveDisplay(m).to_string()).as_deref(), Some("e2e4"));
+
+    uci.command("setoption name BookFile value no/such/book.bin").unwrap();
+    assert!(uci.book.is_none());
+
+    std::fs::remove_file(path).unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, evaluation::evaluate_king_safety, moves::{magic::Magic, tables::AttackTables}, piece::PieceColor, search::values::ATTACK_PENALTY};
+
+fn board(fen: &str) -> Board {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    Board::from_fen(fen, magic, attacks).unwrap()
+}
+
+#[test]
+fn test_king_safety_bare_king() {
+    // a lone king is all the other side has left, nothing can attack the king
+    let board = board("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+
+    assert!(evaluate_king_safety(&board, PieceColor::White) >= 0.0);
+}
+
+#[test]
+fn test_king_safety_attacked_squares() {
+    // the rook attacks d7, e7 and f7 from the seventh rank and none of the king's squares from the sixth
+    let mut seventh = board("4k3/R7/8/8/8/8/8/4K3 w - - 0 1");
+    let mut sixth = board("4k3/8/R7/8/8/8/8/4K3 w - - 0 1");
+
+    // the attacks are filled in by the move generator
+    seventh.get_legal_moves();
+    sixth.get_legal_moves();
+
+    let penalty = evaluate_king_safety(&sixth, PieceColor::Black) - evaluate_king_safety(&seventh, PieceColor::Black);
+
+    assert!(penalty > 0.0 && penalty <= 3.0 * ATTACK_PENALTY);
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use mchess::{board::{Board, FenError}, moves::{magic::Magic, tables::AttackTables}, piece::PieceColor};
+
+// the perft suite positions, with en passant squares only where a pawn can take
+const CORPUS: [&str; 8] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1",
+];
+
+fn from_fen(fen: &str) -> Board {
+    Board::from_fen(fen, Arc::new(Magic::new()), Arc::new(AttackTables::new())).unwrap()
+}
+
+fn round_trip(board: &Board) {
+    let fen = board.to_fen();
+
+    let parsed = Board::from_fen(&fen, board.magic.clone(), board.attacks.clone()).unwrap();
+
+    assert!(parsed == *board, "{} doesn't round trip", fen);
+}
+
+fn round_trip_tree(board: &mut Board, depth: u32) {
+    round_trip(board);
+
+    if depth == 0 {
+        return;
+    }
+
+    for m in board.get_legal_moves() {
+        let state = board.make_move(m);
+        round_trip_tree(board, depth - 1);
+        board.unmake_move(&state);
+    }
+}
+
+#[test]
+fn test_corpus() {
+    for fen in CORPUS {
+        assert_eq!(from_fen(fen).to_fen(), fen);
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    for fen in CORPUS {
+        round_trip_tree(&mut from_fen(fen), 2);
+    }
+}
+
+#[test]
+fn test_en_passant() {
+    // nothing can take on e3, so the square is left out
+    assert_eq!(from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").to_fen(), "4k3/8/8/8/4P3/8/8/4K3 b - - 0 1");
+
+    let mut board = from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    board.make_move(board.parse_uci_string("e2e4").unwrap());
+
+    assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+}
+
+#[test]
+fn test_errors() {
+    let parse = |fen: &str| Board::from_fen(fen, Arc::new(Magic::new()), Arc::new(AttackTables::new())).err();
+
+    assert_eq!(parse("8/8/8/8/8/8/8/8"), Some(FenError::FieldCount(1)));
+    assert_eq!(parse("4k3/8/8/8/8/8/4K3 w - - 0 1"), Some(FenError::RankCount(7)));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K4 w - - 0 1"), Some(FenError::RankLength { rank: 1, length: 9 }));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K2 w - - 0 1"), Some(FenError::RankLength { rank: 1, length: 7 }));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4X3 w - - 0 1"), Some(FenError::InvalidPiece('X')));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/8 w - - 0 1"), Some(FenError::KingCount(PieceColor::White)));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K3 x - - 0 1"), Some(FenError::SideToMove("x".to_string())));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K3 w KK - 0 1"), Some(FenError::Castling("KK".to_string())));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K3 w - e3 0 1"), Some(FenError::EnPassant("e3".to_string())));
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K3 w - - x 1"), Some(FenError::Clock("x".to_string())));
+
+    // the clocks may be left out
+    assert_eq!(parse("4k3/8/8/8/8/8/8/4K3 w - -"), None);
+}
\ No newline at end of file
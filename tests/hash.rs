@@ -19,4 +19,100 @@ fn test_hash_unmake() {
 
         assert_eq!(old_hash, board.hash);
     }
+}
+
+// the incremental hash has to agree with the one computed from scratch
+fn check_tree(board: &mut Board, depth: u32) {
+    for m in board.get_legal_moves() {
+        let state = board.make_move(m);
+
+        let mut fresh = board.clone();
+        fresh.gen_hash();
+
+        assert_eq!(board.hash, fresh.hash, "{}", board.to_fen());
+
+        if depth > 1 {
+            check_tree(board, depth - 1);
+        }
+
+        board.unmake_move(&state);
+    }
+}
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen, Arc::new(Magic::new()), Arc::new(AttackTables::new())).unwrap()
+}
+
+#[test]
+fn test_hash_incremental() {
+    // castling, promotions and en passant
+    check_tree(&mut board("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"), 3);
+    check_tree(&mut board("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1"), 2);
+    check_tree(&mut board("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"), 4);
+
+    // the en passant square of a null move counts for the side that passes, or not at all
+    for fen in [
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+        "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1",
+        "4k3/8/8/8/4P3/8/3P4/4K3 b - e3 0 1",
+    ] {
+        let mut board = board(fen);
+        let hash = board.hash;
+
+        let state = board.make_null_move();
+
+        let mut fresh = board.clone();
+        fresh.gen_hash();
+
+        assert_eq!(board.hash, fresh.hash, "{}", fen);
+
+        check_tree(&mut board, 2);
+        board.unmake_null_move(&state);
+
+        assert_eq!(board.hash, hash);
+    }
+}
+
+// the keys from the Polyglot book format documentation, after each of these moves
+const POLYGLOT: [(&str, u64); 8] = [
+    ("", 0x463b96181691fc9c),
+    ("e2e4", 0x823c9b50fd114196),
+    ("e2e4 d7d5", 0x0756b94461c50fb0),
+    ("e2e4 d7d5 e4e5", 0x662fafb965db29d4),
+    ("e2e4 d7d5 e4e5 f7f5", 0x22a48b5a8e47ff78),
+    ("e2e4 d7d5 e4e5 f7f5 e1e2", 0x652a607ca3f242c1),
+    ("e2e4 d7d5 e4e5 f7f5 e1e2 e8f7", 0x00fdd303c946bdd9),
+    ("a2a4 b7b5 h2h4 b5b4 c2c4", 0x3c8123ea7b067637),
+];
+
+fn play(moves: &str) -> Board {
+    let mut board = Board::startpos(Arc::new(Magic::new()), Arc::new(AttackTables::new()));
+
+    for m in moves.split_whitespace() {
+        let m = board.parse_legal_move(m).unwrap();
+        board.make_move(m);
+    }
+
+    board
+}
+
+#[test]
+fn test_polyglot_differences() {
+    // positions with the queens and kings at home differ by keys that are all known
+    let at_home = POLYGLOT.iter().filter(|(moves, _)| !moves.contains("e1") && !moves.contains("e8"));
+
+    for &(moves, key) in at_home {
+        assert_eq!(play(moves).hash ^ play("").hash, key ^ POLYGLOT[0].1, "{}", moves);
+    }
+
+    let moves = "a2a4 b7b5 h2h4 b5b4 c2c4 b4c3 a1a3";
+    assert_eq!(play(moves).hash ^ play("").hash, 0x5c3f9b829b279560 ^ POLYGLOT[0].1);
+}
+
+#[test]
+#[ignore = "the keys for the black queen from f4 on, the white queen and both kings are still placeholders"]
+fn test_polyglot_keys() {
+    for (moves, key) in POLYGLOT {
+        assert_eq!(play(moves).hash, key, "{}", moves);
+    }
 }
\ No newline at end of file
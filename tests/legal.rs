@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, moves::{helper::{is_castling, is_en_passant}, magic::Magic, tables::AttackTables}, uci::{PositionError, Uci}};
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen, Arc::new(Magic::new()), Arc::new(AttackTables::new())).unwrap()
+}
+
+#[test]
+fn test_parse_legal_move() {
+    let board = board("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+    assert!(board.parse_legal_move("e2e4").is_some());
+    assert!(board.parse_legal_move("g1f3").is_some());
+
+    assert_eq!(board.parse_legal_move("e2e5"), None);
+    assert_eq!(board.parse_legal_move("e7e5"), None); // the other side's piece
+    assert_eq!(board.parse_legal_move("e3e4"), None); // nothing there
+    assert_eq!(board.parse_legal_move("e1e2"), None);
+    assert_eq!(board.parse_legal_move("e2e4q"), None);
+    assert_eq!(board.parse_legal_move("e2e4xx"), None);
+    assert_eq!(board.parse_legal_move("e2"), None);
+    assert_eq!(board.parse_legal_move("i2i4"), None);
+}
+
+#[test]
+fn test_promotion() {
+    let board = board("8/P6k/8/8/8/8/8/K7 w - - 0 1");
+
+    assert!(board.parse_legal_move("a7a8q").is_some());
+    assert!(board.parse_legal_move("a7a8n").is_some());
+
+    // a promotion has to name a piece it can promote to
+    assert_eq!(board.parse_legal_move("a7a8"), None);
+    assert_eq!(board.parse_legal_move("a7a8k"), None);
+    assert_eq!(board.parse_legal_move("a7a8p"), None);
+    assert_eq!(board.parse_legal_move("a1a2q"), None);
+}
+
+#[test]
+fn test_castling() {
+    let board = board("r3k2r/8/8/8/8/8/5r2/R3K2R w KQ - 0 1");
+
+    // the king can't pass through f1
+    assert_eq!(board.parse_legal_move("e1g1"), None);
+    assert!(board.parse_legal_move("e1c1").is_some_and(is_castling));
+
+    assert_eq!(self::board("r3k2r/8/8/8/8/8/8/R3K1NR w KQ - 0 1").parse_legal_move("e1g1"), None);
+    assert_eq!(self::board("r3k2r/8/8/8/8/8/8/R3K2R w Q - 0 1").parse_legal_move("e1g1"), None);
+}
+
+#[test]
+fn test_en_passant() {
+    let mut board = board("4k3/8/8/3pP3/1B6/8/8/4K3 w - d6 0 1");
+
+    assert!(board.parse_legal_move("e5d6").is_some_and(is_en_passant));
+
+    // only a pawn takes en passant, the bishop just moves to the square
+    let m = board.parse_legal_move("b4d6").unwrap();
+
+    assert!(!is_en_passant(m));
+
+    board.make_move(m);
+
+    assert_eq!(board.to_fen(), "4k3/8/3B4/3pP3/8/8/8/4K3 b - - 1 1");
+}
+
+#[test]
+fn test_position_moves() {
+    let mut uci = Uci::new();
+
+    // the moves after the illegal one are ignored
+    assert_eq!(uci.handle_position(&["startpos", "moves", "e2e4", "e7e5", "e1e3", "g1f3"]), Err(PositionError::IllegalMove("e1e3".to_string())));
+    assert_eq!(uci.board.to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2");
+
+    assert_eq!(uci.handle_position(&["startpos", "moves", "e2e4", "e7e5", "g1f3"]), Ok(()));
+    assert_eq!(uci.board.to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+
+    uci.command("position startpos moves e2e4 e2e4").unwrap();
+    assert_eq!(uci.board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+}
\ No newline at end of file
@@ -1,2 +1,17 @@
+mod book;
+mod evaluation;
+mod fen;
 mod hash;
-mod perft;
\ No newline at end of file
+mod legal;
+mod movegen;
+mod options;
+mod outcome;
+mod perft;
+mod pgn;
+mod picker;
+mod repetition;
+mod san;
+mod search;
+mod see;
+mod time;
+mod tt;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, moves::{helper::{is_capture, is_en_passant, is_promotion}, list::MoveList, magic::Magic, tables::AttackTables, Move}, perft::walk};
+
+fn sorted(moves: MoveList) -> Vec<Move> {
+    let mut moves = moves.to_vec();
+    moves.sort_unstable();
+    moves
+}
+
+// compares every specialized generator against the full one
+fn check_generators(board: &mut Board) {
+    let all = sorted(board.get_legal_moves());
+
+    let captures = sorted(board.get_legal_captures());
+    let quiets = sorted(board.get_legal_quiets());
+
+    assert!(captures.iter().all(|&m| is_capture(m) || is_en_passant(m) || is_promotion(m)));
+    assert!(quiets.iter().all(|m| !captures.contains(m)));
+    assert_eq!(sorted(captures.iter().chain(&quiets).copied().collect()), all);
+
+    if board.is_checked(board.turn) {
+        assert_eq!(sorted(board.get_legal_evasions()), all);
+    }
+
+    let quiet_checks = sorted(board.get_legal_quiet_checks());
+
+    let expected = quiets.into_iter().filter(|&m| {
+        let state = board.make_move(m);
+        let check = board.is_checked(board.turn);
+        board.unmake_move(&state);
+
+        check
+    }).collect::<Vec<_>>();
+
+    assert_eq!(quiet_checks, expected);
+}
+
+fn test_fen(fen: &str, depth: u32, expected: usize) {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::from_fen(fen, magic, attacks).unwrap();
+
+    assert_eq!(walk(&mut board, depth, &mut check_generators), expected);
+}
+
+#[test]
+fn test_startpos() {
+    test_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 3, 8902);
+}
+
+#[test]
+fn test_kiwipete() {
+    test_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 3, 97862);
+}
+
+#[test]
+fn test_en_passant_pins() {
+    test_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4, 43238);
+}
+
+#[test]
+fn test_checks_and_promotions() {
+    test_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 3, 9467);
+    test_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", 2, 1486);
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use mchess::{options::{OptionError, OptionValue, Options}, uci::Uci};
+
+#[test]
+fn test_set_values() {
+    let mut options = Options::new();
+
+    options.set("Hash", Some("64")).unwrap();
+    options.set("ponder", Some("true")).unwrap();
+    options.set("BookFile", Some("/books/my book.bin")).unwrap();
+
+    assert_eq!(options.spin("Hash"), 64);
+    assert!(options.check("Ponder"));
+    assert_eq!(options.string("BookFile"), "/books/my book.bin");
+
+    assert_eq!(options.set("Clear Hash", None).unwrap().value, OptionValue::Button);
+}
+
+#[test]
+fn test_invalid_values() {
+    let mut options = Options::new();
+
+    assert_eq!(options.set("Hash", Some("0")).unwrap_err(), OptionError::OutOfRange { name: "Hash".to_owned(), value: 0, min: 1, max: 65536 });
+    assert_eq!(options.set("Threads", Some("two")).unwrap_err(), OptionError::InvalidValue { name: "Threads".to_owned(), value: "two".to_owned() });
+    assert_eq!(options.set("Ponder", Some("yes")).unwrap_err(), OptionError::InvalidValue { name: "Ponder".to_owned(), value: "yes".to_owned() });
+    assert_eq!(options.set("MultiPV", None).unwrap_err(), OptionError::MissingValue("MultiPV".to_owned()));
+    assert_eq!(options.set("Contempt", Some("10")).unwrap_err(), OptionError::Unknown("Contempt".to_owned()));
+
+    // there are no tablebases to point to
+    assert_eq!(options.set("SyzygyPath", Some("/tb")).unwrap_err(), OptionError::Unknown("SyzygyPath".to_owned()));
+
+    // failed updates keep the previous value
+    assert_eq!(options.spin("Hash"), 16);
+    assert!(!options.check("Ponder"));
+}
+
+#[test]
+fn test_setoption_command() {
+    let mut uci = Uci::new();
+
+    uci.command("setoption name Hash value 1").unwrap();
+    uci.command("setoption name Move Overhead value 50").unwrap();
+    uci.command("setoption name Material Weight value 150").unwrap();
+    uci.command("setoption name UCI_Chess960 value true").unwrap();
+    uci.command("setoption name Clear Hash").unwrap();
+
+    assert_eq!(uci.options.spin("Hash"), 1);
+    assert_eq!(uci.options.spin("Move Overhead"), 50);
+    assert!(!uci.options.check("UCI_Chess960"));
+
+    let search = uci.search.lock().unwrap();
+    assert_eq!(search.tt.size(), 1024 * 1024 / 64 * 4);
+    assert_eq!(search.weights.material, 1.5);
+}
\ No newline at end of file
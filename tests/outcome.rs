@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use mchess::{board::{Board, Outcome}, moves::{magic::Magic, tables::AttackTables}, piece::PieceColor, search::Search};
+
+fn outcome(fen: &str) -> Option<Outcome> {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    Board::from_fen(fen, magic, attacks).unwrap().outcome()
+}
+
+#[test]
+fn test_checkmate() {
+    assert_eq!(outcome("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1"), Some(Outcome::Checkmate(PieceColor::White)));
+    assert_eq!(outcome("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"), Some(Outcome::Checkmate(PieceColor::Black)));
+}
+
+#[test]
+fn test_stalemate() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", magic, attacks).unwrap();
+
+    assert!(board.is_stalemate());
+    assert!(!board.is_checkmate());
+    assert_eq!(board.outcome(), Some(Outcome::Stalemate));
+}
+
+#[test]
+fn test_draw_rules() {
+    assert_eq!(outcome("8/8/4k3/8/8/3NK3/8/8 w - - 0 1"), Some(Outcome::InsufficientMaterial));
+    assert_eq!(outcome("8/8/4k3/8/8/3RK3/8/8 w - - 100 80"), Some(Outcome::FiftyMoves));
+    assert_eq!(outcome("8/8/4k3/8/8/3RK3/8/8 w - - 99 80"), None);
+}
+
+#[test]
+fn test_search_stalemate() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", magic, attacks).unwrap();
+    let mut search = Search::new();
+
+    let result = search.search(&mut board, 3);
+
+    assert_eq!(result.value, 0);
+    assert!(result.moves.is_empty());
+}
\ No newline at end of file
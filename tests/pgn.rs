@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use mchess::{display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}, pgn::{Game, GameResult, PgnError, MainLineReader}};
+
+fn read(text: &str) -> Vec<Result<Game, PgnError>> {
+    MainLineReader::new(text, Arc::new(Magic::new()), Arc::new(AttackTables::new())).collect()
+}
+
+fn uci(game: &Game) -> String {
+    game.moves.iter().map(|&m| MoveDisplay(m).to_string()).collect::<Vec<_>>().join(" ")
+}
+
+const GAMES: &str = r#"[Event "F/S Return Match"]
+[Site "Belgrade, Serbia JUG"]
+[Date "1992.11.04"]
+[Round "29"]
+[White "Fischer, Robert J."]
+[Black "Spassky, Boris V."]
+[Result "1/2-1/2"]
+
+1. e4 e5 2. Nf3 Nc6 3. Bb5 {This opening is called the Ruy Lopez.} 3... a6
+4. Ba4 Nf6 5. O-O Be7 6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 Nb8 10. d4 Nbd7
+11. c4 c6 12. cxb5 axb5 13. Nc3 Bb7 14. Bg5 b4 15. Nb1 h6 16. Bh4 c5 17. dxe5
+Nxe4 18. Bxe7 Qxe7 19. exd6 Qf6 20. Nbd2 Nxd6 21. Nc4 Nxc4 22. Bxc4 Nb6
+23. Ne5 Rae8 24. Bxf7+ Rxf7 25. Nxf7 Rxe1+ 26. Qxe1 Kxf7 27. Qe3 Qg5 28. Qxg5
+hxg5 29. b3 Ke6 30. a3 Kd6 31. axb4 cxb4 32. Ra5 Nd5 33. f3 Bc8 34. Kf2 Bf5
+35. Ra7 g6 36. Ra6+ Kc5 37. Ke1 Nf4 38. g3 Nxh3 39. Kd2 Kb5 40. Rd6 Kc5 41. Ra6
+Nf2 42. g4 Bd3 43. Re6 1/2-1/2
+
+% an escaped line, ignored like the comments
+[Event "?"]
+[White "A \"quoted\" name"]
+[Result "0-1"]
+
+1. f3 $2 e5 (1... d5 {a [bracket] or ( is fine} (1... c5 2. e4) 2. e4) 2. g4?? ; the worst move
+Qh4# 0-1
+"#;
+
+#[test]
+fn test_read() {
+    let games = read(GAMES).into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(games.len(), 2);
+
+    assert_eq!(games[0].tag("White"), Some("Fischer, Robert J."));
+    assert_eq!(games[0].tag("Round"), Some("29"));
+    assert_eq!(games[0].moves.len(), 85);
+    assert_eq!(games[0].result, GameResult::Draw);
+
+    // only the main line is kept
+    assert_eq!(games[1].tag("White"), Some("A \"quoted\" name"));
+    assert_eq!(uci(&games[1]), "f2f3 e7e5 g2g4 d8h4");
+    assert_eq!(games[1].result, GameResult::BlackWin);
+
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = games[1].replay(magic, attacks).unwrap();
+    assert!(board.is_checkmate());
+}
+
+#[test]
+fn test_annotations() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let text = "1. e4! e5?! 2. Nf3 $1 {the main line} Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4 Be7) exd4) 3. Bb5 a6 ; Ruy Lopez\n4. Ba4 $14 *";
+    let game = MainLineReader::new(text, magic.clone(), attacks.clone()).next().unwrap().unwrap();
+
+    assert_eq!(uci(&game), "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4");
+
+    // only the main line is written back
+    assert!(game.to_pgn(magic, attacks).unwrap().ends_with("\n1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 4. Ba4 *\n"));
+}
+
+#[test]
+fn test_write() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let text = "1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 *";
+    let mut game = MainLineReader::new(text, magic.clone(), attacks.clone()).next().unwrap().unwrap();
+
+    // a game without tags still gets the seven tag roster
+    assert_eq!(game.to_pgn(magic.clone(), attacks.clone()).unwrap(), concat!(
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n",
+        "[White \"?\"]\n[Black \"?\"]\n[Result \"*\"]\n\n",
+        "1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 *\n"
+    ));
+
+    game.set_tag("White", "mchess");
+    game.set_tag("Annotator", "\\o/");
+    game.result = GameResult::WhiteWin;
+
+    let pgn = game.to_pgn(magic, attacks).unwrap();
+
+    assert!(pgn.starts_with("[Event \"?\"]\n"));
+    assert!(pgn.contains("[White \"mchess\"]\n"));
+    assert!(pgn.contains("[Result \"1-0\"]\n[Annotator \"\\\\o/\"]\n\n"));
+    assert!(pgn.ends_with("a6 1-0\n"));
+}
+
+#[test]
+fn test_round_trip() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let positions = [
+        GAMES,
+        // black moves first, so the numbering starts with an ellipsis
+        "[FEN \"r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 20\"]\n\n20... O-O-O 21. O-O Rd2 *"
+    ];
+
+    for text in positions {
+        for game in MainLineReader::new(text, magic.clone(), attacks.clone()) {
+            let mut game = game.unwrap();
+
+            // the writer fills in the roster tags a game doesn't have
+            for name in ["Event", "Site", "Date", "Round", "White", "Black", "Result"] {
+                if game.tag(name).is_none() {
+                    game.set_tag(name, "?");
+                }
+            }
+
+            let pgn = game.to_pgn(magic.clone(), attacks.clone()).unwrap();
+
+            assert!(pgn.lines().all(|line| line.len() < 80), "{}", pgn);
+
+            let mut parsed = MainLineReader::new(&pgn, magic.clone(), attacks.clone()).next().unwrap().unwrap();
+            parsed.tags.retain(|(name, _)| name != "Result");
+            game.tags.retain(|(name, _)| name != "Result");
+            parsed.tags.sort();
+            game.tags.sort();
+
+            assert_eq!(parsed, game);
+        }
+    }
+
+    let game = MainLineReader::new(positions[1], magic.clone(), attacks.clone()).next().unwrap().unwrap();
+
+    assert!(game.to_pgn(magic, attacks).unwrap().ends_with("\n20... O-O-O 21. O-O Rd2 *\n"));
+}
+
+#[test]
+fn test_errors() {
+    let games = read("1. e4 e5 2. Ke3 Nc6 1-0\n\n1. d4 d5 ) 0-1\n\n[Event unquoted]\n1. d4 *\n\n1. c4 c5 *");
+
+    // every broken game is skipped up to its result
+    assert_eq!(games.len(), 4);
+    assert_eq!(games[0], Err(PgnError::IllegalMove { line: 1, san: "Ke3".to_string() }));
+    assert_eq!(games[1], Err(PgnError::Variation(3)));
+    assert_eq!(games[2], Err(PgnError::Tag(5)));
+    assert_eq!(games[3].as_ref().map(uci), Ok("c2c4 c7c5".to_string()));
+
+    assert_eq!(read("1. e4 { never closed"), vec![Err(PgnError::UnexpectedEnd)]);
+    assert!(read("").is_empty());
+}
+
+#[test]
+fn test_en_passant_suffix() {
+    for text in ["1. e4 a6 2. e5 d5 3. exd6 e.p. *", "1. e4 a6 2. e5 d5 3. exd6e.p. *"] {
+        let games = read(text);
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].as_ref().map(uci), Ok("e2e4 a7a6 e4e5 d7d5 e5d6".to_string()), "{}", text);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, moves::{magic::Magic, tables::AttackTables, Move}, perft::walk, search::{picker::MovePicker, Search}};
+
+fn sorted(mut moves: Vec<Move>) -> Vec<Move> {
+    moves.sort_unstable();
+    moves
+}
+
+// whatever the transposition table and the refutations suggest, the stages together have
+// to hand out every legal move exactly once and a legal table move before anything else
+fn check_picker(search: &Search, board: &mut Board) {
+    let all = sorted(board.get_legal_moves().to_vec());
+
+    let captures = board.get_legal_captures();
+    let quiets = board.get_legal_quiets();
+
+    // the moves of the other side stand in for entries of some other position
+    let state = board.make_null_move();
+    let foreign = board.get_legal_moves();
+    board.unmake_null_move(&state);
+
+    let nth = |moves: &[Move], i: usize| moves.get(i).or(moves.first()).copied();
+
+    for tt_move in [None, nth(&captures, 0), nth(&quiets, 0), nth(&foreign, 0)] {
+        let refutations = [
+            [None, None, None],
+            [nth(&quiets, 1), nth(&captures, 1), nth(&foreign, 1)],
+            [tt_move, nth(&quiets, 0), nth(&quiets, 0)],
+        ];
+
+        for refutations in refutations {
+            let mut picker = MovePicker::new(tt_move, refutations);
+            let mut picked = vec![];
+
+            while let Some(m) = picker.next(search, board) {
+                picked.push(m);
+            }
+
+            if let Some(m) = tt_move.filter(|m| all.contains(m)) {
+                assert_eq!(picked.first(), Some(&m));
+            }
+
+            assert_eq!(sorted(picked), all, "{:?} {:?}", tt_move, refutations);
+        }
+    }
+}
+
+#[test]
+fn test_picker() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let search = Search::new();
+
+    for (fen, depth, nodes) in [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 3, 8902),
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 2, 2039),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 3, 2812),
+        ("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 2, 264),
+    ] {
+        let mut board = Board::from_fen(fen, magic.clone(), attacks.clone()).unwrap();
+
+        assert_eq!(walk(&mut board, depth, &mut |board| check_picker(&search, board)), nodes, "{}", fen);
+    }
+}
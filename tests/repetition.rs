@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, moves::{magic::Magic, tables::AttackTables}};
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for m in moves {
+        let m = board.parse_uci_string(m).unwrap();
+        board.make_move(m);
+    }
+}
+
+#[test]
+fn test_threefold_repetition() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::startpos(magic, attacks);
+
+    play(&mut board, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+
+    assert!(board.is_repetition(1));
+    assert!(!board.is_draw());
+
+    play(&mut board, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+
+    assert!(board.is_repetition(2));
+    assert!(board.is_draw());
+}
+
+#[test]
+fn test_repetition_reset_by_pawn_move() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::startpos(magic, attacks);
+
+    play(&mut board, &["g1f3", "g8f6", "f3g1", "f6g8", "e2e3", "e7e6", "g1f3", "g8f6", "f3g1", "f6g8"]);
+
+    assert!(board.is_repetition(1));
+    assert!(!board.is_repetition(2));
+}
+
+#[test]
+fn test_history_unmake() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::startpos(magic, attacks);
+
+    let moves = board.get_legal_moves();
+
+    for m in moves {
+        let state = board.make_move(m);
+        assert_eq!(board.history.len(), 1);
+
+        board.unmake_move(&state);
+        assert!(board.history.is_empty());
+    }
+}
\ No newline at end of file
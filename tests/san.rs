@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}};
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen, Arc::new(Magic::new()), Arc::new(AttackTables::new())).unwrap()
+}
+
+fn san(fen: &str, m: &str) -> String {
+    let mut board = board(fen);
+    let m = board.parse_uci_string(m).unwrap();
+
+    board.to_san(m)
+}
+
+fn parse(fen: &str, san: &str) -> Option<String> {
+    let mut board = board(fen);
+
+    board.parse_san(san).map(|m| MoveDisplay(m).to_string())
+}
+
+// every legal move has to come back from its own notation
+fn round_trip(board: &mut Board, depth: u32) {
+    for m in board.get_legal_moves() {
+        let san = board.to_san(m);
+
+        assert_eq!(board.parse_san(&san), Some(m), "{} in {}", san, board.to_fen());
+
+        if depth > 1 {
+            let state = board.make_move(m);
+            round_trip(board, depth - 1);
+            board.unmake_move(&state);
+        }
+    }
+}
+
+const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[test]
+fn test_to_san() {
+    assert_eq!(san(STARTPOS, "e2e4"), "e4");
+    assert_eq!(san(STARTPOS, "g1f3"), "Nf3");
+
+    assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), "exd6");
+    assert_eq!(san("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1"), "O-O");
+    assert_eq!(san("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1c1"), "O-O-O");
+
+    assert_eq!(san("8/P6k/8/8/8/8/8/K7 w - - 0 1", "a7a8q"), "a8=Q");
+    assert_eq!(san("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "a7b8n"), "axb8=N");
+    assert_eq!(san("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "a7b8q"), "axb8=Q+");
+
+    // fool's mate
+    assert_eq!(san("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2", "d8h4"), "Qh4#");
+}
+
+#[test]
+fn test_disambiguation() {
+    assert_eq!(san("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1", "b1d2"), "Nbd2");
+    assert_eq!(san("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1", "f3d2"), "Nfd2");
+
+    assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3"), "R1a3");
+    assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a5a3"), "R5a3");
+
+    // neither the file nor the rank alone tells the queen on h4 apart
+    assert_eq!(san("1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1", "h4e1"), "Qh4e1");
+    assert_eq!(san("1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1", "e4e1"), "Qee1");
+    assert_eq!(san("1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1", "h1e1"), "Q1e1");
+
+    // a pinned knight doesn't make the other one ambiguous
+    assert_eq!(san("4k3/8/8/8/8/5N2/8/rN2K3 w - - 0 1", "f3d2"), "Nd2");
+}
+
+#[test]
+fn test_parse_san() {
+    assert_eq!(parse(STARTPOS, "e4").as_deref(), Some("e2e4"));
+    assert_eq!(parse(STARTPOS, "Nf3!?").as_deref(), Some("g1f3"));
+    assert_eq!(parse(STARTPOS, "Ng1f3").as_deref(), Some("g1f3"));
+    assert_eq!(parse(STARTPOS, "Ng1-f3").as_deref(), Some("g1f3"));
+
+    assert_eq!(parse("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "0-0").as_deref(), Some("e1g1"));
+    assert_eq!(parse("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "O-O-O+").as_deref(), Some("e1c1"));
+
+    assert_eq!(parse("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "ed6").as_deref(), Some("e5d6"));
+    assert_eq!(parse("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "ab8Q").as_deref(), Some("a7b8q"));
+    assert_eq!(parse("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "axb8=Q#").as_deref(), Some("a7b8q"));
+    assert_eq!(parse("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "axb8=b").as_deref(), Some("a7b8b"));
+
+    // ambiguous, illegal, or missing the promotion
+    assert_eq!(parse("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1", "Nd2"), None);
+    assert_eq!(parse(STARTPOS, "e5"), None);
+    assert_eq!(parse(STARTPOS, "O-O"), None);
+    assert_eq!(parse("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "axb8"), None);
+    assert_eq!(parse("1n5k/P7/8/8/8/8/8/K7 w - - 0 1", "axb8=K"), None);
+    assert_eq!(parse(STARTPOS, ""), None);
+}
+
+#[test]
+fn test_round_trip() {
+    round_trip(&mut board("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"), 2);
+    round_trip(&mut board("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1"), 2);
+    round_trip(&mut board("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8"), 2);
+}
\ No newline at end of file
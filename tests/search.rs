@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, display::MoveDisplay, moves::{magic::Magic, tables::AttackTables}, search::{limits::SearchLimits, pruning::Pruning, score::{mate_distance, mate_in}, time::TimeManager, Search}};
+
+fn board(fen: &str) -> Board {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    Board::from_fen(fen, magic, attacks).unwrap()
+}
+
+fn search(fen: &str, depth: u8) -> (String, i32) {
+    let mut board = board(fen);
+    let mut search = Search::new();
+
+    let result = search.search(&mut board, depth);
+
+    (format!("{}", MoveDisplay(result.moves[0])), result.value)
+}
+
+#[test]
+fn test_quiescence() {
+    // a depth one search stops right after Qxd5, only the quiescence search sees exd5
+    let (m, value) = search("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1", 1);
+
+    assert_ne!(m, "d1d5");
+    assert!(value > 0);
+
+    // without the defender the pawn is free
+    let (m, _) = search("4k3/8/8/3p4/8/8/8/3QK3 w - - 0 1", 1);
+
+    assert_eq!(m, "d1d5");
+}
+
+#[test]
+fn test_mate_in_one() {
+    let (m, value) = search("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1", 3);
+
+    assert_eq!(m, "d1d8");
+    assert_eq!(value, mate_in(1));
+    assert_eq!(mate_distance(value), Some(1));
+}
+
+#[test]
+fn test_mate_in_one_black() {
+    let (m, value) = search("3r2k1/5ppp/8/8/8/8/5PPP/6K1 b - - 0 1", 3);
+
+    assert_eq!(m, "d8d1");
+    assert_eq!(mate_distance(value), Some(1));
+}
+
+#[test]
+fn test_mate_in_two() {
+    let (_, value) = search("k7/8/2K5/8/8/8/8/7R w - - 0 1", 4);
+
+    assert_eq!(mate_distance(value), Some(2));
+}
+
+#[test]
+fn test_getting_mated() {
+    let (_, value) = search("k7/8/1K6/8/8/8/8/7R b - - 0 1", 4);
+
+    assert_eq!(mate_distance(value), Some(-1));
+}
+
+#[test]
+fn test_node_limit() {
+    let mut board = board("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP1B1PPP/R2QKB1R w KQ - 0 8");
+    let mut search = Search::new();
+
+    let result = search.go(&mut board, SearchLimits::nodes(20000));
+
+    assert_eq!(search.nodes, 20000);
+    assert!(!result.moves.is_empty());
+}
+
+#[test]
+fn test_mate_limit() {
+    let mut board = board("k7/8/2K5/8/8/8/8/7R w - - 0 1");
+    let mut search = Search::new();
+
+    // without any other limit the search only ends once the mate is found
+    let result = search.go(&mut board, SearchLimits::mate(2));
+
+    assert_eq!(mate_distance(result.value), Some(2));
+}
+
+#[test]
+fn test_search_moves() {
+    let mut board = board("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1");
+    let mut search = Search::new();
+
+    let search_moves = ["g2g3", "h2h4"].map(|m| board.parse_uci_string(m).unwrap());
+
+    let result = search.go(&mut board, SearchLimits {
+        search_moves: search_moves.to_vec(),
+        ..SearchLimits::depth(3)
+    });
+
+    assert!(search_moves.contains(&result.moves[0]));
+    assert!(mate_distance(result.value).is_none());
+}
+
+#[test]
+fn test_multi_pv() {
+    let mut position = board("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1");
+    let mut search = Search::new();
+    search.multi_pv = 3;
+
+    let results = search.go_multi_pv(&mut position, SearchLimits::depth(3));
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(format!("{}", MoveDisplay(results[0].moves[0])), "d1d8");
+    assert_eq!(results[0].value, mate_in(1));
+
+    // every line starts with a different move and the lines are ordered by score
+    assert!(results[0].moves[0] != results[1].moves[0] && results[1].moves[0] != results[2].moves[0] && results[0].moves[0] != results[2].moves[0]);
+    assert!(results[0].value >= results[1].value && results[1].value >= results[2].value);
+
+    // there are only as many lines as there are legal moves, Kxb2 is the only one here
+    let mut single = board("k7/8/8/8/8/8/1r6/K1r5 w - - 0 1");
+    assert_eq!(search.go_multi_pv(&mut single, SearchLimits::depth(3)).len(), 1);
+}
+
+#[test]
+fn test_threads() {
+    let mut board = board("k7/8/2K5/8/8/8/8/7R w - - 0 1");
+    let mut search = Search::new();
+    search.threads = 4;
+
+    let result = search.go(&mut board, SearchLimits::depth(6));
+
+    assert_eq!(mate_distance(result.value), Some(2));
+
+    // helper nodes are counted in the total but not in the main thread's own count, searching
+    // for a fixed time makes sure the helpers get to run even when there is only one core
+    let fen = "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP1B1PPP/R2QKB1R w KQ - 0 8";
+    search.go(&mut self::board(fen), SearchLimits::time(TimeManager::fixed(200, 0)));
+
+    assert!(search.total_nodes() > search.nodes);
+}
+
+#[test]
+fn test_pruning() {
+    let fen = "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP1B1PPP/R2QKB1R w KQ - 0 8";
+
+    let mut pruned = Search::new();
+    pruned.go(&mut board(fen), SearchLimits::depth(6));
+
+    let mut unpruned = Search::new();
+    unpruned.pruning = Pruning { lmr: false, futility: false, reverse_futility: false, late_move: false, razoring: false };
+    unpruned.go(&mut board(fen), SearchLimits::depth(6));
+
+    assert!(pruned.nodes < unpruned.nodes);
+
+    // none of the techniques may hide a forced mate
+    for i in 0..5 {
+        let mut search = Search::new();
+        search.pruning = Pruning { lmr: i == 0, futility: i == 1, reverse_futility: i == 2, late_move: i == 3, razoring: i == 4 };
+
+        let result = search.go(&mut board("k7/8/2K5/8/8/8/8/7R w - - 0 1"), SearchLimits::depth(6));
+
+        assert_eq!(mate_distance(result.value), Some(2));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use mchess::{board::Board, moves::{magic::Magic, tables::AttackTables}};
+
+fn see(fen: &str, m: &str) -> i32 {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let board = Board::from_fen(fen, magic, attacks).unwrap();
+    let m = board.parse_uci_string(m).unwrap();
+
+    board.see(m)
+}
+
+#[test]
+fn test_undefended() {
+    assert_eq!(see("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "e1e5"), 100);
+}
+
+#[test]
+fn test_xray() {
+    // the queen behind the bishop and the rook behind the rook join the exchange
+    assert_eq!(see("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1", "d3e5"), 100 - 320);
+    assert_eq!(see("4k3/8/8/3p4/8/8/3R4/3R1K2 w - - 0 1", "d2d5"), 100);
+    assert_eq!(see("3rk3/8/8/3p4/8/8/3R4/3R1K2 w - - 0 1", "d2d5"), 100);
+    assert_eq!(see("3rk3/3r4/8/3p4/8/8/3R4/3R1K2 w - - 0 1", "d2d5"), 100 - 500);
+}
+
+#[test]
+fn test_losing_capture() {
+    assert_eq!(see("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", "d1d5"), 100 - 900);
+    assert_eq!(see("4k3/8/2p5/3p4/4P3/8/8/3QK3 w - - 0 1", "e4d5"), 100);
+}
+
+#[test]
+fn test_king_recapture() {
+    // the king can only take back when the square isn't defended any more
+    assert_eq!(see("8/8/8/8/8/3k4/3p4/3Q3K w - - 0 1", "d1d2"), 100 - 900);
+    assert_eq!(see("8/8/8/8/8/3k4/3p4/3QK3 w - - 0 1", "d1d2"), 100);
+    assert_eq!(see("7k/8/8/8/8/8/3p4/4K3 w - - 0 1", "e1d2"), 100);
+}
+
+#[test]
+fn test_en_passant_and_promotion() {
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+    assert_eq!(see("4k3/3P4/8/8/8/8/8/4K3 w - - 0 1", "d7d8q"), -100);
+    assert_eq!(see("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8q"), 500 + 900 - 100 - 900);
+    assert_eq!(see("3rk3/4P3/8/8/8/8/8/3RK3 w - - 0 1", "e7d8q"), 500 + 900 - 100);
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use std::{sync::Arc, time::Instant};
+
+use mchess::{board::Board, moves::{magic::Magic, tables::AttackTables}, search::{time::TimeManager, Search, SearchResult}};
+
+#[test]
+fn test_limits() {
+    let fixed = TimeManager::fixed(1000, 50);
+    assert_eq!((fixed.soft_limit(), fixed.hard_limit()), (Some(950), Some(950)));
+
+    let clock = TimeManager::clock(60000, 0, None, 10);
+    assert_eq!(clock.soft_limit(), Some(59990 / 30));
+    assert_eq!(clock.hard_limit(), Some(59990 / 30 * 4));
+
+    // the last move before the time control may not use everything that is left
+    let clock = TimeManager::clock(10000, 0, Some(1), 0);
+    assert_eq!((clock.soft_limit(), clock.hard_limit()), (Some(2000), Some(5000)));
+
+    let clock = TimeManager::clock(5, 0, None, 10);
+    assert_eq!((clock.soft_limit(), clock.hard_limit()), (Some(1), Some(1)));
+
+    // pondering lengthens the soft limit only
+    let clock = TimeManager::clock(60000, 0, None, 0).with_ponder();
+    assert_eq!((clock.soft_limit(), clock.hard_limit()), (Some(2500), Some(8000)));
+
+    let infinite = TimeManager::infinite();
+    assert_eq!((infinite.soft_limit(), infinite.hard_limit()), (None, None));
+    assert!(!infinite.hard_limit_reached());
+}
+
+#[test]
+fn test_adjustments() {
+    let result = |value, m| SearchResult { value, moves: vec![m] };
+
+    let mut stable = TimeManager::clock(60000, 0, None, 0);
+    for _ in 0..5 {
+        stable.update(&result(20, 1));
+    }
+    assert!(stable.soft_limit().unwrap() < 2000);
+
+    let mut changing = TimeManager::clock(60000, 0, None, 0);
+    for m in 0..5 {
+        changing.update(&result(20, m));
+    }
+    assert!(changing.soft_limit().unwrap() > 2000);
+
+    let mut dropping = TimeManager::clock(60000, 0, None, 0);
+    for value in [20, 20, 20, -80] {
+        dropping.update(&result(value, 1));
+    }
+    assert!(dropping.soft_limit().unwrap() > 2000);
+    assert!(dropping.soft_limit() <= dropping.hard_limit());
+
+    // a fixed move time is never shortened
+    let mut fixed = TimeManager::fixed(1000, 0);
+    for _ in 0..5 {
+        fixed.update(&result(20, 1));
+    }
+    assert_eq!(fixed.soft_limit(), Some(1000));
+}
+
+#[test]
+fn test_hard_limit() {
+    let magic = Arc::new(Magic::new());
+    let attacks = Arc::new(AttackTables::new());
+
+    let mut board = Board::from_fen("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP1B1PPP/R2QKB1R w KQ - 0 8", magic, attacks).unwrap();
+    let mut search = Search::new();
+
+    let start = Instant::now();
+    let result = search.iterative_deepening(&mut board, u8::MAX, TimeManager::fixed(200, 0));
+
+    assert!(start.elapsed().as_millis() < 400);
+    assert!(!result.moves.is_empty());
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use mchess::search::{score::{mate_in, mated_in}, tt::TranspositionTable, Node, NodeType};
+
+fn node(depth: u8, node_type: NodeType, value: i32, best_move: Option<u32>) -> Node {
+    Node {
+        depth,
+        node_type,
+        value,
+        best_move
+    }
+}
+
+#[test]
+fn test_store_probe() {
+    let tt = TranspositionTable::new(1);
+
+    tt.store(0x1234_5678_9ABC_DEF0, node(5, NodeType::Cut, -153, Some(0x2A5C)));
+    tt.store(0x0FED_CBA9_8765_4321, node(12, NodeType::PV, mated_in(7), None));
+    tt.store(0x1111_2222_3333_4444, node(3, NodeType::All, mate_in(3), Some(0x1234)));
+
+    let entry = tt.probe(0x1234_5678_9ABC_DEF0).unwrap();
+    assert_eq!((entry.depth, entry.node_type, entry.value, entry.best_move), (5, NodeType::Cut, -153, Some(0x2A5C)));
+
+    let entry = tt.probe(0x0FED_CBA9_8765_4321).unwrap();
+    assert_eq!((entry.depth, entry.node_type, entry.value, entry.best_move), (12, NodeType::PV, mated_in(7), None));
+
+    let entry = tt.probe(0x1111_2222_3333_4444).unwrap();
+    assert_eq!((entry.depth, entry.node_type, entry.value, entry.best_move), (3, NodeType::All, mate_in(3), Some(0x1234)));
+
+    assert!(tt.probe(0x5555_6666_7777_8888).is_none());
+}
+
+#[test]
+fn test_key_verification() {
+    // a single bucket, so every key maps to the same entries
+    let tt = TranspositionTable::new(0);
+
+    tt.store(1, node(4, NodeType::PV, 10, None));
+
+    assert!(tt.probe(1).is_some());
+    assert!(tt.probe(2).is_none());
+}
+
+#[test]
+fn test_replacement() {
+    let tt = TranspositionTable::new(0);
+
+    tt.store(1, node(8, NodeType::Cut, 10, Some(0x100)));
+
+    // a shallower bound from the same search doesn't overwrite a deeper entry
+    tt.store(1, node(2, NodeType::Cut, 20, None));
+    assert_eq!(tt.probe(1).unwrap().depth, 8);
+
+    // but the best move is kept when a newer search replaces it
+    tt.new_search();
+    tt.store(1, node(2, NodeType::All, 20, None));
+
+    let entry = tt.probe(1).unwrap();
+    assert_eq!((entry.depth, entry.best_move), (2, Some(0x100)));
+
+    // a full bucket evicts the shallowest, oldest entry
+    for key in 2..=4 {
+        tt.store(key, node(key as u8 * 3, NodeType::PV, 0, None));
+    }
+
+    tt.store(5, node(10, NodeType::PV, 0, None));
+
+    assert!(tt.probe(1).is_none());
+    assert!(tt.probe(5).is_some());
+}
+
+#[test]
+fn test_clear_hashfull() {
+    let mut tt = TranspositionTable::new(1);
+    assert_eq!(tt.hashfull(), 0);
+
+    tt.new_search();
+
+    for key in 0..100_000u64 {
+        tt.store(key.wrapping_mul(0x9E37_79B9_7F4A_7C15), node(1, NodeType::PV, 0, None));
+    }
+
+    assert!(tt.hashfull() > 500);
+
+    tt.clear();
+    assert_eq!(tt.hashfull(), 0);
+
+    tt.resize(2);
+    assert_eq!(tt.size(), 2 * 1024 * 1024 / 16);
+}
\ No newline at end of file